@@ -0,0 +1,86 @@
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::geometry::{Quad, Sphere, World};
+use crate::materials::{Conductor, DiffuseLight, Lambertian};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::textures::{Solid, Texture, XYZChecker};
+use crate::utils::{Color, Point3, Vec3, Vec3Ext};
+
+// Turns the tangent of a unit square around its center, for concentric brushing
+#[derive(Debug)]
+struct Concentric;
+
+impl Texture for Concentric {
+    fn sample(&self, u: f64, v: f64, _: &Vec3) -> Color {
+        let angle = (v - 0.5).atan2(u - 0.5) + PI / 2.0;
+        Color::constant(angle.rem_euclid(2.0 * PI) / (2.0 * PI))
+    }
+}
+
+pub fn example_brushed_metal() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_texture(Rc::new(XYZChecker::from_colors(
+        0.5,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(0.8, 0.8, 0.8),
+    ))));
+    let material_light = Rc::new(DiffuseLight::from_color(Color::new(6.0, 6.0, 6.0)));
+
+    let mut world = World::new(vec![
+        Box::new(Quad::new(
+            Point3::new(-10.0, 0.0, -10.0),
+            Vec3::new(0.0, 0.0, 20.0),
+            Vec3::new(20.0, 0.0, 0.0),
+            material_ground,
+        )),
+        Box::new(Quad::new(
+            Point3::new(-0.5, 5.0, -0.5),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            material_light,
+        )),
+    ]);
+
+    // Brushed along the parallels and along the meridians
+    world.add(Box::new(Sphere::new(
+        Point3::new(-1.8, 0.6, 0.0),
+        0.6,
+        Rc::new(Conductor::aluminium(0.35).with_anisotropy(0.9)),
+    )));
+    world.add(Box::new(Sphere::new(
+        Point3::new(1.8, 0.6, 0.0),
+        0.6,
+        Rc::new(
+            Conductor::copper(0.35)
+                .with_anisotropy(0.9)
+                .with_rotation(Rc::new(Solid::new(Color::constant(0.25)))),
+        ),
+    )));
+
+    // Concentric brushing on a plate, like the bottom of a pan
+    world.add(Box::new(Quad::new(
+        Point3::new(-0.8, 0.3, 0.8),
+        Vec3::new(1.6, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, -1.6),
+        Rc::new(
+            Conductor::chrome(0.3)
+                .with_anisotropy(1.0)
+                .with_rotation(Rc::new(Concentric)),
+        ),
+    )));
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 30.0,
+        look_from: Point3::new(0.0, 4.0, 7.0),
+        look_at: Point3::new(0.0, 0.4, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
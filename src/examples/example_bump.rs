@@ -0,0 +1,99 @@
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::geometry::{Quad, Sphere, World};
+use crate::materials::{Bumped, Conductor, DiffuseLight, Lambertian};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::textures::{PerlinNoise, Texture, XYZChecker};
+use crate::utils::{Color, Point3, Vec3};
+
+// Tangent space normals of a grid of round dimples, standing in for a normal map image
+#[derive(Debug)]
+struct Dimples;
+
+impl Texture for Dimples {
+    fn sample(&self, u: f64, v: f64, _: &Vec3) -> Color {
+        let (x, y) = ((u * 8.0).fract() * 2.0 - 1.0, (v * 8.0).fract() * 2.0 - 1.0);
+        let r = (x * x + y * y).sqrt();
+        let normal = if r < 0.8 {
+            // Slopes of a spherical cap pressed into the surface
+            let slope = (r / 0.8 * PI / 2.0).sin() * 0.6;
+            Vec3::new(x / r.max(1e-6) * slope, y / r.max(1e-6) * slope, 1.0).normalize()
+        } else {
+            Vec3::new(0.0, 0.0, 1.0)
+        };
+        0.5 * (normal + Color::new(1.0, 1.0, 1.0))
+    }
+}
+
+pub fn example_bump() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_texture(Rc::new(XYZChecker::from_colors(
+        0.5,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(0.8, 0.8, 0.8),
+    ))));
+    let material_light = Rc::new(DiffuseLight::from_color(Color::new(6.0, 6.0, 6.0)));
+
+    let mut world = World::new(vec![
+        Box::new(Quad::new(
+            Point3::new(-10.0, 0.0, -10.0),
+            Vec3::new(0.0, 0.0, 20.0),
+            Vec3::new(20.0, 0.0, 0.0),
+            material_ground,
+        )),
+        Box::new(Quad::new(
+            Point3::new(-3.0, 5.0, -1.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            material_light,
+        )),
+    ]);
+
+    // Hammered copper from solid noise
+    world.add(Box::new(Sphere::new(
+        Point3::new(-1.5, 0.6, 0.0),
+        0.6,
+        Rc::new(Bumped::height_map(
+            Rc::new(Conductor::copper(0.2)),
+            Rc::new(PerlinNoise::new_bw(0.15, 1.0)),
+            0.002,
+        )),
+    )));
+
+    // Coarse stucco, also from noise
+    world.add(Box::new(Sphere::new(
+        Point3::new(0.0, 0.6, 0.0),
+        0.6,
+        Rc::new(Bumped::height_map(
+            Rc::new(Lambertian::from_albedo(Color::new(0.6, 0.5, 0.4))),
+            Rc::new(PerlinNoise::new_bw(1.0, 1.0)),
+            0.03,
+        )),
+    )));
+
+    // Dimpled panel from a normal map
+    world.add(Box::new(Quad::new(
+        Point3::new(0.9, 0.0, 0.3),
+        Vec3::new(1.2, 0.0, -0.6),
+        Vec3::new(0.0, 1.2, 0.0),
+        Rc::new(Bumped::normal_map(
+            Rc::new(Conductor::aluminium(0.3)),
+            Rc::new(Dimples),
+        )),
+    )));
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 30.0,
+        look_from: Point3::new(0.0, 3.0, 8.0),
+        look_at: Point3::new(0.0, 0.5, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
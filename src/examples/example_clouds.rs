@@ -0,0 +1,83 @@
+use std::rc::Rc;
+
+use crate::geometry::{HeterogeneousMedium, MediumConfig, Quad, Voxel, VoxelGrid, World};
+use crate::lights::PointLight;
+use crate::materials::Lambertian;
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::utils::{Color, Perlin, Point3, Vec3, Vec3Ext};
+
+pub fn example_clouds() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_albedo(Color::new(0.4, 0.5, 0.3)));
+
+    // Voxel Grids
+    let cloud = Rc::new(VoxelGrid::from_perlin([64, 32, 64], 4.0));
+
+    let perlin = Perlin::default();
+    let fireball = Rc::new(VoxelGrid::from_fn([48, 48, 48], |p| {
+        let r = 2.0 * (p - Point3::constant(0.5)).norm();
+        let noise = perlin.turb(&(p * 6.0), 5);
+        let density = (1.0 - r + 0.5 * noise).clamp(0.0, 1.0);
+        Voxel {
+            density,
+            temperature: 2500.0 * density * (1.0 - r).max(0.0),
+            emission: 0.0,
+        }
+    }));
+
+    // Objects
+    let ground = Quad::new(
+        Point3::new(-50.0, 0.0, 50.0),
+        Vec3::new(100.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, -100.0),
+        material_ground,
+    );
+
+    let cloud = HeterogeneousMedium::new(
+        cloud,
+        Point3::new(-4.0, 3.0, -3.0),
+        Point3::new(2.0, 6.0, 3.0),
+        MediumConfig {
+            density: 4.0,
+            albedo: Color::new(0.99, 0.99, 0.99),
+            anisotropy: 0.6,
+            ..MediumConfig::default()
+        },
+    );
+
+    let fireball = HeterogeneousMedium::new(
+        fireball,
+        Point3::new(1.5, 0.0, 0.0),
+        Point3::new(4.0, 2.5, 2.5),
+        MediumConfig {
+            density: 6.0,
+            albedo: Color::new(0.3, 0.3, 0.3),
+            blackbody_intensity: 0.5,
+            ..MediumConfig::default()
+        },
+    );
+
+    // World
+    let mut world = World::new(vec![Box::new(ground), Box::new(cloud), Box::new(fireball)]);
+
+    // Lights
+    world.add_light(Rc::new(PointLight::from_blackbody(
+        Point3::new(-2.0, 12.0, 2.0),
+        5500.0,
+        150.0,
+    )));
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 50.0,
+        look_from: Point3::new(0.0, 3.0, 12.0),
+        look_at: Point3::new(0.0, 2.5, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
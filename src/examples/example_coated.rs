@@ -0,0 +1,85 @@
+use std::rc::Rc;
+
+use crate::geometry::{Quad, Sphere, World};
+use crate::materials::{Coated, Conductor, DiffuseLight, Lambertian, Material, Metal};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::textures::{PerlinNoise, Solid, XYZChecker};
+use crate::utils::{Color, Point3, Vec3, Vec3Ext};
+
+pub fn example_coated() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_texture(Rc::new(XYZChecker::from_colors(
+        0.5,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(0.8, 0.8, 0.8),
+    ))));
+    let material_light = Rc::new(DiffuseLight::from_color(Color::new(6.0, 6.0, 6.0)));
+
+    let mut world = World::new(vec![
+        Box::new(Quad::new(
+            Point3::new(-10.0, 0.0, -10.0),
+            Vec3::new(0.0, 0.0, 20.0),
+            Vec3::new(20.0, 0.0, 0.0),
+            material_ground,
+        )),
+        Box::new(Quad::new(
+            Point3::new(-2.0, 5.0, -1.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            material_light,
+        )),
+    ]);
+
+    // Car paint, varnished wood, coated gold, satin plastic and lacquered chrome
+    let car_paint = Coated::new(
+        Rc::new(Lambertian::from_albedo(Color::new(0.5, 0.02, 0.03))),
+        1.5,
+    );
+    let wood = Rc::new(Lambertian::from_texture(Rc::new(PerlinNoise::new(
+        0.3,
+        2.0,
+        Color::new(0.3, 0.15, 0.06),
+        Color::new(0.6, 0.4, 0.2),
+    ))));
+    let varnish = Coated::new(wood, 1.5)
+        .with_thickness(0.05)
+        .with_tint(Color::new(0.9, 0.75, 0.5));
+    let gold = Coated::new(Rc::new(Conductor::gold(0.4)), 1.5);
+    let satin = Coated::new(
+        Rc::new(Lambertian::from_albedo(Color::new(0.1, 0.2, 0.6))),
+        1.5,
+    )
+    .with_roughness(Rc::new(Solid::new(Color::constant(0.35))));
+    let chrome = Coated::new(Rc::new(Metal::new(Color::new(0.8, 0.8, 0.8), 0.0)), 1.5)
+        .with_thickness(0.02)
+        .with_tint(Color::new(0.6, 0.8, 0.9));
+
+    let materials: [Rc<dyn Material>; 5] = [
+        Rc::new(car_paint),
+        Rc::new(varnish),
+        Rc::new(gold),
+        Rc::new(satin),
+        Rc::new(chrome),
+    ];
+    for (i, material) in materials.into_iter().enumerate() {
+        world.add(Box::new(Sphere::new(
+            Point3::new(-2.4 + 1.2 * i as f64, 0.5, 0.0),
+            0.5,
+            material,
+        )));
+    }
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 30.0,
+        look_from: Point3::new(0.0, 3.0, 8.0),
+        look_at: Point3::new(0.0, 0.4, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
@@ -0,0 +1,126 @@
+use rand::prelude::{Rng, SeedableRng, StdRng};
+use std::rc::Rc;
+
+use crate::geometry::{Quad, Sphere, World};
+use crate::materials::{Cutout, DiffuseLight, Lambertian, Metal};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::textures::{Solid, Texture, XYZChecker};
+use crate::utils::{Color, Point3, Vec3, Vec3Ext};
+
+// Diagonal wires of a chain-link fence, standing in for the alpha channel of an image
+#[derive(Debug)]
+struct ChainLink;
+
+impl Texture for ChainLink {
+    fn sample(&self, u: f64, v: f64, _: &Vec3) -> Color {
+        let wire = |x: f64| ((x * 20.0).fract() - 0.5).abs() > 0.42;
+        Color::constant(if wire(u + v) || wire(u - v + 1.0) {
+            1.0
+        } else {
+            0.0
+        })
+    }
+}
+
+// Outline of a leaf, soft at the edges
+#[derive(Debug)]
+struct Leaf;
+
+impl Texture for Leaf {
+    fn sample(&self, u: f64, v: f64, _: &Vec3) -> Color {
+        let (x, y) = (2.0 * u - 1.0, 2.0 * v - 1.0);
+        let width = 0.5 * (1.0 - y * y);
+        Color::constant((1.0 - x.abs() / width.max(1e-6)).clamp(0.0, 1.0) * 4.0)
+    }
+}
+
+pub fn example_cutout() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_texture(Rc::new(XYZChecker::from_colors(
+        0.5,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(0.8, 0.8, 0.8),
+    ))));
+    let material_light = Rc::new(DiffuseLight::from_color(Color::new(6.0, 6.0, 6.0)));
+
+    let mut world = World::new(vec![
+        Box::new(Quad::new(
+            Point3::new(-10.0, 0.0, -10.0),
+            Vec3::new(0.0, 0.0, 20.0),
+            Vec3::new(20.0, 0.0, 0.0),
+            material_ground,
+        )),
+        Box::new(Quad::new(
+            Point3::new(-1.0, 5.0, -1.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            material_light,
+        )),
+        Box::new(Sphere::new(
+            Point3::new(0.0, 0.7, -1.5),
+            0.7,
+            Rc::new(Lambertian::from_albedo(Color::new(0.7, 0.3, 0.2))),
+        )),
+    ]);
+
+    // Fence with sharp wires in front
+    world.add(Box::new(Quad::new(
+        Point3::new(-1.8, 0.0, 0.0),
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.5, 0.0),
+        Rc::new(
+            Cutout::new(
+                Rc::new(Metal::new(Color::constant(0.6), 0.3)),
+                Rc::new(ChainLink),
+            )
+            .with_threshold(0.5),
+        ),
+    )));
+
+    // Sheer curtain, half of the light passing through
+    world.add(Box::new(Quad::new(
+        Point3::new(0.4, 0.0, 0.5),
+        Vec3::new(1.4, 0.0, -0.4),
+        Vec3::new(0.0, 1.6, 0.0),
+        Rc::new(Cutout::new(
+            Rc::new(Lambertian::from_albedo(Color::new(0.9, 0.9, 0.8))),
+            Rc::new(Solid::new(Color::constant(0.5))),
+        )),
+    )));
+
+    // Leaves scattered on the ground, alpha tested
+    let leaf = Rc::new(
+        Cutout::new(
+            Rc::new(Lambertian::from_albedo(Color::new(0.3, 0.5, 0.1))),
+            Rc::new(Leaf),
+        )
+        .with_threshold(0.5),
+    );
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..40 {
+        let center = Point3::new(rng.gen_range(-3.0..3.0), 0.01, rng.gen_range(0.5..2.5));
+        let angle = rng.gen_range(0.0..std::f64::consts::PI);
+        let u = 0.3 * Vec3::new(angle.cos(), 0.0, angle.sin());
+        let v = 0.3 * Vec3::new(-angle.sin(), 0.0, angle.cos());
+        world.add(Box::new(Quad::new(
+            center - 0.5 * (u + v),
+            u,
+            v,
+            leaf.clone(),
+        )));
+    }
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 30.0,
+        look_from: Point3::new(0.0, 2.5, 8.0),
+        look_at: Point3::new(0.0, 0.6, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
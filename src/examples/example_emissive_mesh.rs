@@ -0,0 +1,51 @@
+use std::rc::Rc;
+
+use crate::geometry::{Background, Sphere, TriangleMesh, World};
+use crate::materials::{DiffuseLight, Lambertian, Metal};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::textures::UVImage;
+use crate::utils::{Color, Point3, Vec3};
+
+pub fn example_emissive_mesh() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_albedo(Color::new(0.5, 0.5, 0.5)));
+    let material_sphere = Rc::new(Metal::new(Color::new(0.8, 0.8, 0.8), 0.1));
+    let material_sign = Rc::new(DiffuseLight::from_texture(Rc::new(UVImage::new(
+        "assets/textures/sign.png",
+    ))));
+
+    // Objects
+    let ground = Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        material_ground.clone(),
+    );
+
+    let sphere = Sphere::new(Point3::new(0.0, 0.5, 0.0), 0.5, material_sphere.clone());
+
+    // Every triangle of the sign is registered as an area light
+    let sign = TriangleMesh::new(
+        "assets/meshes/sign.obj",
+        Point3::new(0.0, 0.0, -1.0),
+        1.0,
+        material_sign.clone(),
+    );
+
+    // World
+    let mut world = World::new(vec![Box::new(ground), Box::new(sphere), Box::new(sign)]);
+    world.background = Background::Solid(Color::zeros());
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 40.0,
+        look_from: Point3::new(0.0, 1.5, 5.0),
+        look_at: Point3::new(0.0, 0.8, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
@@ -8,7 +8,7 @@
 // pub fn example_frost() -> (World, ViewportConfig, LensConfig) {
 //     // Materials
 //     let material_ground = Rc::new(Lambertian::from_albedo(Vec3::new(0.03, 0.03, 0.03)));
-//     let material_center = Rc::new(Dielectric::frosted(1.5, 0.1));
+//     let material_center = Rc::new(Dielectric::rough(1.5, 0.3));
 //     let material_left = Rc::new(Lambertian::from_albedo(Vec3::new(0.8, 0.9, 1.0)));
 //     let material_right = Rc::new(Metal::new(Vec3::new(0.95, 0.95, 0.95), 0.0));
 //
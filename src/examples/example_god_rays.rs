@@ -0,0 +1,75 @@
+use std::rc::Rc;
+
+use crate::geometry::{Atmosphere, Background, Quad, Sphere, World};
+use crate::lights::PointLight;
+use crate::materials::Lambertian;
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::utils::{Color, Point3, Vec3};
+
+pub fn example_god_rays() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_floor = Rc::new(Lambertian::from_albedo(Color::new(0.6, 0.6, 0.6)));
+    let material_wall = Rc::new(Lambertian::from_albedo(Color::new(0.7, 0.65, 0.6)));
+    let material_sphere = Rc::new(Lambertian::from_albedo(Color::new(0.2, 0.4, 0.8)));
+
+    // Wall with a window from x = -1 to 1 and y = 2 to 4, split by a cross into four panes
+    let wall = |q: Point3, width: f64, height: f64| -> Box<Quad> {
+        Box::new(Quad::new(
+            q,
+            Vec3::new(width, 0.0, 0.0),
+            Vec3::new(0.0, height, 0.0),
+            material_wall.clone(),
+        ))
+    };
+
+    let mut world = World::new(vec![
+        Box::new(Quad::new(
+            Point3::new(-20.0, 0.0, 20.0),
+            Vec3::new(40.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, -40.0),
+            material_floor,
+        )),
+        Box::new(Quad::new(
+            Point3::new(-20.0, 8.0, -2.0),
+            Vec3::new(40.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 22.0),
+            material_wall.clone(),
+        )),
+        wall(Point3::new(-20.0, 0.0, -2.0), 19.0, 8.0),
+        wall(Point3::new(1.0, 0.0, -2.0), 19.0, 8.0),
+        wall(Point3::new(-1.0, 0.0, -2.0), 2.0, 2.0),
+        wall(Point3::new(-1.0, 4.0, -2.0), 2.0, 4.0),
+        wall(Point3::new(-0.05, 2.0, -2.0), 0.1, 2.0),
+        wall(Point3::new(-1.0, 2.95, -2.0), 2.0, 0.1),
+        Box::new(Sphere::new(
+            Point3::new(0.5, 0.6, 2.5),
+            0.6,
+            material_sphere,
+        )),
+    ]);
+    world.background = Background::Solid(Color::zeros());
+
+    // Fog settling on the floor
+    world.atmosphere = Some(Atmosphere::with_height_falloff(0.08, 0.01, 0.6, 0.0, 0.2));
+
+    // Lights
+    world.add_light(Rc::new(PointLight::from_blackbody(
+        Point3::new(6.0, 27.0, -40.0),
+        5500.0,
+        20000.0,
+    )));
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 55.0,
+        look_from: Point3::new(6.0, 2.0, 9.0),
+        look_at: Point3::new(-0.5, 1.8, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
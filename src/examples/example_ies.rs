@@ -0,0 +1,83 @@
+use std::rc::Rc;
+
+use crate::geometry::{Background, Quad, Sphere, World};
+use crate::lights::{IesProfile, PointLight, SpotLight};
+use crate::materials::{DiffuseLight, Lambertian};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::utils::{Color, Illuminant, Point3, Vec3};
+
+pub fn example_ies() -> (World, ViewportConfig, LensConfig) {
+    // Profiles
+    let downlight = Rc::new(IesProfile::new("assets/ies/downlight.ies"));
+
+    // Materials
+    let material_wall = Rc::new(Lambertian::from_albedo(Color::new(0.73, 0.73, 0.73)));
+    let material_sphere = Rc::new(Lambertian::from_albedo(Color::new(0.8, 0.3, 0.2)));
+    let material_panel = Rc::new(DiffuseLight::from_ies(
+        Color::new(4.0, 4.0, 4.0),
+        downlight.clone(),
+    ));
+
+    // Objects
+    let floor = Quad::new(
+        Point3::new(-5.0, 0.0, 5.0),
+        Vec3::new(10.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, -10.0),
+        material_wall.clone(),
+    );
+
+    let back_wall = Quad::new(
+        Point3::new(-5.0, 0.0, -2.0),
+        Vec3::new(10.0, 0.0, 0.0),
+        Vec3::new(0.0, 6.0, 0.0),
+        material_wall.clone(),
+    );
+
+    let panel = Quad::new(
+        Point3::new(2.0, 4.0, -1.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        material_panel.clone(),
+    );
+
+    let sphere = Sphere::new(Point3::new(0.0, 0.75, 0.0), 0.75, material_sphere.clone());
+
+    // World
+    let mut world = World::new(vec![
+        Box::new(floor),
+        Box::new(back_wall),
+        Box::new(panel),
+        Box::new(sphere),
+    ]);
+    world.background = Background::Solid(Color::zeros());
+
+    // Lights
+    world.add_light(Rc::new(PointLight::from_ies(
+        Point3::new(-2.5, 4.0, -1.0),
+        Vec3::new(0.0, -1.0, 0.0),
+        Illuminant::Halogen.color() * 20.0,
+        downlight.clone(),
+    )));
+    world.add_light(Rc::new(SpotLight::from_blackbody(
+        Point3::new(0.0, 5.0, 3.0),
+        Point3::new(0.0, 0.75, 0.0),
+        5000.0,
+        30.0,
+        20.0,
+        15.0,
+    )));
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 50.0,
+        look_from: Point3::new(0.0, 2.5, 8.0),
+        look_at: Point3::new(0.0, 1.5, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
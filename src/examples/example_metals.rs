@@ -0,0 +1,65 @@
+use std::rc::Rc;
+
+use crate::geometry::{Quad, Sphere, World};
+use crate::materials::{Conductor, DiffuseLight, Lambertian};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::textures::XYZChecker;
+use crate::utils::{Color, Point3, Vec3};
+
+pub fn example_metals() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_texture(Rc::new(XYZChecker::from_colors(
+        0.5,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(0.8, 0.8, 0.8),
+    ))));
+    let material_light = Rc::new(DiffuseLight::from_color(Color::new(6.0, 6.0, 6.0)));
+
+    let mut world = World::new(vec![
+        Box::new(Quad::new(
+            Point3::new(-10.0, 0.0, -10.0),
+            Vec3::new(0.0, 0.0, 20.0),
+            Vec3::new(20.0, 0.0, 0.0),
+            material_ground,
+        )),
+        Box::new(Quad::new(
+            Point3::new(-2.0, 5.0, -1.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            material_light,
+        )),
+    ]);
+
+    // Presets from left to right, polished in front and brushed behind
+    let presets: [fn(f64) -> Conductor; 5] = [
+        Conductor::gold,
+        Conductor::silver,
+        Conductor::copper,
+        Conductor::aluminium,
+        Conductor::chrome,
+    ];
+    for (i, preset) in presets.iter().enumerate() {
+        let x = -2.4 + 1.2 * i as f64;
+        for (z, roughness) in [(0.7, 0.15), (-0.7, 0.5)] {
+            world.add(Box::new(Sphere::new(
+                Point3::new(x, 0.5, z),
+                0.5,
+                Rc::new(preset(roughness)),
+            )));
+        }
+    }
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 30.0,
+        look_from: Point3::new(0.0, 3.0, 8.0),
+        look_at: Point3::new(0.0, 0.4, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
@@ -0,0 +1,93 @@
+use std::rc::Rc;
+
+use crate::geometry::{Quad, Sphere, World};
+use crate::materials::{Conductor, DiffuseLight, Lambertian, Mix};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::textures::{PerlinNoise, Solid, UVChecker, XYZChecker};
+use crate::utils::{Color, Point3, Vec3};
+
+pub fn example_mix() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_texture(Rc::new(XYZChecker::from_colors(
+        0.5,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(0.8, 0.8, 0.8),
+    ))));
+    let material_light = Rc::new(DiffuseLight::from_color(Color::new(6.0, 6.0, 6.0)));
+
+    let mut world = World::new(vec![
+        Box::new(Quad::new(
+            Point3::new(-10.0, 0.0, -10.0),
+            Vec3::new(0.0, 0.0, 20.0),
+            Vec3::new(20.0, 0.0, 0.0),
+            material_ground,
+        )),
+        Box::new(Quad::new(
+            Point3::new(-2.0, 5.0, -1.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            material_light,
+        )),
+    ]);
+
+    // Rust patches on brushed iron, where the noise is high contrast
+    let rust = Rc::new(Lambertian::from_texture(Rc::new(PerlinNoise::new(
+        0.5,
+        1.0,
+        Color::new(0.25, 0.08, 0.02),
+        Color::new(0.5, 0.2, 0.05),
+    ))));
+    let rusted = Mix::new(
+        Rc::new(Conductor::aluminium(0.25)),
+        rust,
+        Rc::new(PerlinNoise::new_bw(1.5, 4.0)),
+    );
+
+    // Half tarnished copper, blended everywhere
+    let tarnished = Mix::new(
+        Rc::new(Conductor::copper(0.1)),
+        Rc::new(Lambertian::from_albedo(Color::new(0.2, 0.45, 0.35))),
+        Rc::new(Solid::new(Color::new(0.5, 0.5, 0.5))),
+    );
+
+    // Gold inlay chosen by a checker mask
+    let inlay = Mix::new(
+        Rc::new(Lambertian::from_albedo(Color::new(0.05, 0.05, 0.1))),
+        Rc::new(Conductor::gold(0.2)),
+        Rc::new(UVChecker::from_colors(
+            0.1,
+            Color::zeros(),
+            Color::new(1.0, 1.0, 1.0),
+        )),
+    );
+
+    world.add(Box::new(Sphere::new(
+        Point3::new(-1.3, 0.6, 0.0),
+        0.6,
+        Rc::new(rusted),
+    )));
+    world.add(Box::new(Sphere::new(
+        Point3::new(0.0, 0.6, 0.0),
+        0.6,
+        Rc::new(tarnished),
+    )));
+    world.add(Box::new(Sphere::new(
+        Point3::new(1.3, 0.6, 0.0),
+        0.6,
+        Rc::new(inlay),
+    )));
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 30.0,
+        look_from: Point3::new(0.0, 3.0, 8.0),
+        look_at: Point3::new(0.0, 0.5, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
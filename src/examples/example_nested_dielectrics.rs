@@ -0,0 +1,66 @@
+use std::rc::Rc;
+
+use crate::geometry::{Sphere, World};
+use crate::materials::{Dielectric, Lambertian};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::utils::{Color, Point3, Vec3};
+
+pub fn example_nested_dielectrics() -> (World, ViewportConfig, LensConfig) {
+    // Materials, the innermost medium gets the highest priority
+    let material_ground = Rc::new(Lambertian::from_albedo(Color::new(0.5, 0.5, 0.5)));
+    let material_back = Rc::new(Lambertian::from_albedo(Color::new(0.8, 0.3, 0.1)));
+    let material_glass = Rc::new(
+        Dielectric::new(1.5)
+            .with_priority(1)
+            .with_transmittance(Color::new(0.8, 0.95, 0.85), 0.1),
+    );
+    let material_water = Rc::new(
+        Dielectric::new(1.33)
+            .with_priority(2)
+            .with_transmittance(Color::new(0.55, 0.8, 0.95), 0.5),
+    );
+    let material_air = Rc::new(Dielectric::new(1.0).with_priority(3));
+
+    // Objects
+    let ground = Sphere::new(
+        Point3::new(0.0, -100.5, -1.0),
+        100.0,
+        material_ground.clone(),
+    );
+
+    let back_sphere = Sphere::new(Point3::new(0.3, 0.0, -2.5), 0.5, material_back.clone());
+
+    // Solid glass sphere with water inside, only the outer 0.05 remain glass
+    let glass_sphere = Sphere::new(Point3::new(0.0, 0.0, -1.0), 0.5, material_glass.clone());
+    let water_sphere = Sphere::new(Point3::new(0.0, 0.0, -1.0), 0.45, material_water.clone());
+
+    // Air bubbles in the water, one of them cutting into the glass
+    let bubble_small = Sphere::new(Point3::new(-0.15, 0.1, -0.85), 0.08, material_air.clone());
+    let bubble_large = Sphere::new(Point3::new(0.15, -0.2, -0.9), 0.12, material_air.clone());
+    let bubble_wall = Sphere::new(Point3::new(0.0, 0.38, -0.95), 0.1, material_air.clone());
+
+    // World
+    let world = World::new(vec![
+        Box::new(ground),
+        Box::new(back_sphere),
+        Box::new(glass_sphere),
+        Box::new(water_sphere),
+        Box::new(bubble_small),
+        Box::new(bubble_large),
+        Box::new(bubble_wall),
+    ]);
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 30.0,
+        look_from: Point3::new(0.0, 0.5, 1.5),
+        look_at: Point3::new(0.0, 0.0, -1.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
@@ -0,0 +1,105 @@
+use std::rc::Rc;
+
+use crate::geometry::{Quad, Sphere, World};
+use crate::materials::{DiffuseLight, Lambertian, Principled, PrincipledConfig};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::textures::{Solid, Texture, XYZChecker};
+use crate::utils::{Color, Point3, Vec3, Vec3Ext};
+
+fn solid(color: Color) -> Rc<dyn Texture> {
+    Rc::new(Solid::new(color))
+}
+
+pub fn example_principled() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_texture(Rc::new(XYZChecker::from_colors(
+        0.5,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(0.8, 0.8, 0.8),
+    ))));
+    let material_light = Rc::new(DiffuseLight::from_color(Color::new(6.0, 6.0, 6.0)));
+
+    let mut world = World::new(vec![
+        Box::new(Quad::new(
+            Point3::new(-10.0, 0.0, -10.0),
+            Vec3::new(0.0, 0.0, 20.0),
+            Vec3::new(20.0, 0.0, 0.0),
+            material_ground,
+        )),
+        Box::new(Quad::new(
+            Point3::new(-2.0, 5.0, -1.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            material_light,
+        )),
+    ]);
+
+    // Metallic from 0 to 1 in front
+    let copper = Color::new(0.95, 0.64, 0.54);
+    let mut configs: Vec<PrincipledConfig> = (0..5)
+        .map(|i| PrincipledConfig {
+            base_color: solid(copper),
+            metallic: solid(Color::constant(i as f64 / 4.0)),
+            roughness: solid(Color::constant(0.3)),
+            ..PrincipledConfig::default()
+        })
+        .collect();
+
+    // Rough plastic, clear coat, sheen, glass and emission behind
+    let red = Color::new(0.7, 0.1, 0.1);
+    configs.extend([
+        PrincipledConfig {
+            base_color: solid(red),
+            roughness: solid(Color::constant(0.8)),
+            ..PrincipledConfig::default()
+        },
+        PrincipledConfig {
+            base_color: solid(red),
+            roughness: solid(Color::constant(0.8)),
+            clearcoat: solid(Color::ones()),
+            ..PrincipledConfig::default()
+        },
+        PrincipledConfig {
+            base_color: solid(Color::new(0.1, 0.1, 0.4)),
+            roughness: solid(Color::ones()),
+            specular: solid(Color::zeros()),
+            sheen: solid(Color::ones()),
+            ..PrincipledConfig::default()
+        },
+        PrincipledConfig {
+            base_color: solid(Color::new(0.8, 0.95, 0.85)),
+            roughness: solid(Color::constant(0.1)),
+            transmission: solid(Color::ones()),
+            ..PrincipledConfig::default()
+        },
+        PrincipledConfig {
+            base_color: solid(Color::constant(0.2)),
+            emission: Some(solid(Color::new(2.0, 1.2, 0.4))),
+            ..PrincipledConfig::default()
+        },
+    ]);
+
+    for (i, config) in configs.into_iter().enumerate() {
+        let x = -2.4 + 1.2 * (i % 5) as f64;
+        let z = if i < 5 { 0.7 } else { -0.7 };
+        world.add(Box::new(Sphere::new(
+            Point3::new(x, 0.5, z),
+            0.5,
+            Rc::new(Principled::new(config)),
+        )));
+    }
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 30.0,
+        look_from: Point3::new(0.0, 3.0, 8.0),
+        look_at: Point3::new(0.0, 0.4, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
@@ -0,0 +1,82 @@
+use std::rc::Rc;
+
+use crate::geometry::{Background, Quad, Sphere, Triangle, World};
+use crate::materials::{Dielectric, DiffuseLight, Lambertian};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::utils::{Color, Dispersion, Point3, Vec3};
+
+pub fn example_prism() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_wall = Rc::new(Lambertian::from_albedo(Color::new(0.05, 0.05, 0.05)));
+    let material_strip = Rc::new(DiffuseLight::from_color(Color::new(4.0, 4.0, 4.0)));
+    let material_prism = Rc::new(Dielectric::dispersive(Dispersion::sf11()));
+    let material_sphere = Rc::new(Dielectric::dispersive(Dispersion::Cauchy(1.67, 0.0743)));
+
+    // Dark wall with bright horizontal strips, seen through the prism
+    let mut world = World::new(vec![Box::new(Quad::new(
+        Point3::new(-8.0, -6.0, -3.0),
+        Vec3::new(16.0, 0.0, 0.0),
+        Vec3::new(0.0, 14.0, 0.0),
+        material_wall,
+    ))]);
+    for i in 0..20 {
+        world.add(Box::new(Quad::new(
+            Point3::new(-8.0, -5.5 + 0.6 * i as f64, -2.99),
+            Vec3::new(16.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.12, 0.0),
+            material_strip.clone(),
+        )));
+    }
+    world.background = Background::Solid(Color::zeros());
+
+    // Triangular prism along x, apex up, with outward facing sides
+    let (x0, x1) = (-1.2, 0.4);
+    let corners = [
+        Point3::new(0.0, 1.1, 0.0),
+        Point3::new(0.0, 0.1, 0.55),
+        Point3::new(0.0, 0.1, -0.55),
+    ];
+    let centroid = (corners[0] + corners[1] + corners[2]) / 3.0;
+    for i in 0..3 {
+        let (a, b) = (corners[i], corners[(i + 1) % 3]);
+        let (u, v) = (b - a, Vec3::new(x1 - x0, 0.0, 0.0));
+        let outward = u.cross(&v).dot(&((a + b) / 2.0 - centroid)) > 0.0;
+        let (u, v) = if outward { (u, v) } else { (v, u) };
+        world.add(Box::new(Quad::new(
+            a + Vec3::new(x0, 0.0, 0.0),
+            u,
+            v,
+            material_prism.clone(),
+        )));
+    }
+    for (x, normal) in [(x0, -Vec3::x()), (x1, Vec3::x())] {
+        let offset = Vec3::new(x, 0.0, 0.0);
+        world.add(Box::new(Triangle::new(
+            corners.map(|corner| corner + offset),
+            [normal; 3],
+            [Point3::zeros(); 3],
+            material_prism.clone(),
+        )));
+    }
+
+    // Flint glass ball next to it
+    world.add(Box::new(Sphere::new(
+        Point3::new(1.1, 0.55, 0.0),
+        0.45,
+        material_sphere,
+    )));
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 35.0,
+        look_from: Point3::new(0.0, 0.6, 3.5),
+        look_at: Point3::new(0.0, 0.6, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
@@ -0,0 +1,73 @@
+use std::rc::Rc;
+
+use crate::geometry::{Quad, Sphere, World};
+use crate::materials::{DiffuseLight, Lambertian, Material, OrenNayar};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::textures::{PerlinNoise, XYZChecker};
+use crate::utils::{Color, Point3, Vec3};
+
+pub fn example_rough_diffuse() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_texture(Rc::new(XYZChecker::from_colors(
+        0.5,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(0.8, 0.8, 0.8),
+    ))));
+    let material_light = Rc::new(DiffuseLight::from_color(Color::new(8.0, 8.0, 8.0)));
+
+    // Lit from the side so the flattening at grazing angles shows
+    let mut world = World::new(vec![
+        Box::new(Quad::new(
+            Point3::new(-10.0, 0.0, -10.0),
+            Vec3::new(0.0, 0.0, 20.0),
+            Vec3::new(20.0, 0.0, 0.0),
+            material_ground,
+        )),
+        Box::new(Quad::new(
+            Point3::new(-6.0, 0.5, -1.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(0.0, 3.0, 0.0),
+            material_light,
+        )),
+    ]);
+
+    // Lambertian, then rougher clay, without interreflections last but one, and concrete
+    let clay = Color::new(0.7, 0.45, 0.3);
+    let concrete = Rc::new(PerlinNoise::new(
+        0.2,
+        1.0,
+        Color::new(0.45, 0.45, 0.42),
+        Color::new(0.6, 0.6, 0.58),
+    ));
+    let materials: [Rc<dyn Material>; 5] = [
+        Rc::new(Lambertian::from_albedo(clay)),
+        Rc::new(OrenNayar::from_albedo(clay, 0.5)),
+        Rc::new(OrenNayar::from_albedo(clay, 1.0)),
+        Rc::new(OrenNayar::from_albedo(clay, 1.0).with_multiscatter(false)),
+        Rc::new(OrenNayar::from_texture(
+            concrete,
+            Rc::new(PerlinNoise::new_bw(0.5, 0.5)),
+        )),
+    ];
+    for (i, material) in materials.into_iter().enumerate() {
+        world.add(Box::new(Sphere::new(
+            Point3::new(-2.4 + 1.2 * i as f64, 0.5, 0.0),
+            0.5,
+            material,
+        )));
+    }
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 30.0,
+        look_from: Point3::new(0.0, 3.0, 8.0),
+        look_at: Point3::new(0.0, 0.4, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
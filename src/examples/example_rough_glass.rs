@@ -0,0 +1,67 @@
+use std::rc::Rc;
+
+use crate::geometry::{Quad, Sphere, World};
+use crate::materials::{Dielectric, Lambertian};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::textures::{UVChecker, XYZChecker};
+use crate::utils::{Color, Point3, Vec3, Vec3Ext};
+
+pub fn example_rough_glass() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_albedo(Color::new(0.5, 0.5, 0.5)));
+    let material_wall = Rc::new(Lambertian::from_texture(Rc::new(XYZChecker::from_colors(
+        0.25,
+        Color::new(0.8, 0.2, 0.1),
+        Color::new(0.9, 0.9, 0.9),
+    ))));
+
+    let mut world = World::new(vec![
+        Box::new(Quad::new(
+            Point3::new(-10.0, 0.0, -10.0),
+            Vec3::new(0.0, 0.0, 20.0),
+            Vec3::new(20.0, 0.0, 0.0),
+            material_ground,
+        )),
+        Box::new(Quad::new(
+            Point3::new(-10.0, 0.0, -1.5),
+            Vec3::new(20.0, 0.0, 0.0),
+            Vec3::new(0.0, 6.0, 0.0),
+            material_wall,
+        )),
+    ]);
+
+    // Smooth to frosted from left to right
+    for (i, roughness) in [0.0, 0.1, 0.25, 0.5].into_iter().enumerate() {
+        world.add(Box::new(Sphere::new(
+            Point3::new(-1.8 + 1.2 * i as f64, 0.5, 0.0),
+            0.5,
+            Rc::new(Dielectric::rough(1.5, roughness)),
+        )));
+    }
+
+    // Clear and frosted patches driven by a texture
+    let patches = Rc::new(UVChecker::from_colors(
+        8.0,
+        Color::constant(0.05),
+        Color::constant(0.4),
+    ));
+    world.add(Box::new(Sphere::new(
+        Point3::new(0.0, 0.6, 1.6),
+        0.6,
+        Rc::new(Dielectric::new(1.5).with_roughness(patches)),
+    )));
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 30.0,
+        look_from: Point3::new(0.0, 2.0, 7.0),
+        look_at: Point3::new(0.0, 0.6, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
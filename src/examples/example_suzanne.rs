@@ -15,7 +15,7 @@ pub fn example_suzanne() -> (World, ViewportConfig, LensConfig) {
     // let material_ground = Rc::new(Lambertian::from_albedo(Color::new(0.6, 0.6, 0.8) * 0.7));
     // let material_suzanne = Rc::new(Normal::new());
 
-    let material_suzanne = Rc::new(Dielectric::frosted(1.5, 0.01));
+    let material_suzanne = Rc::new(Dielectric::rough(1.5, 0.1));
     // let material_suzanne = Rc::new(Dielectric::new(1.5));
     // let material_suzanne = Rc::new(Lambertian::from_texture(Rc::new(UVImage::new(
     //     "assets/textures/monkey.png",
@@ -0,0 +1,76 @@
+use std::rc::Rc;
+
+use crate::geometry::{Quad, Sphere, World};
+use crate::materials::{Conductor, Dielectric, Lambertian, Metal, ThinFilm};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::textures::{PerlinNoise, XYZChecker};
+use crate::utils::{Color, Point3, Vec3};
+
+pub fn example_thin_film() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let material_ground = Rc::new(Lambertian::from_texture(Rc::new(XYZChecker::from_colors(
+        0.5,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(0.8, 0.8, 0.8),
+    ))));
+
+    let mut world = World::new(vec![Box::new(Quad::new(
+        Point3::new(-10.0, 0.0, -10.0),
+        Vec3::new(0.0, 0.0, 20.0),
+        Vec3::new(20.0, 0.0, 0.0),
+        material_ground,
+    ))]);
+
+    // Soap bubble, a film of swirling thickness with air on both sides
+    let swirls = PerlinNoise::new(
+        0.4,
+        1.0,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(1.0, 1.0, 1.0),
+    );
+    world.add(Box::new(Sphere::new(
+        Point3::new(-1.7, 0.7, 0.0),
+        0.7,
+        Rc::new(
+            Dielectric::new(1.0)
+                .with_thin_film(ThinFilm::soap(600.0).with_thickness_map(Rc::new(swirls))),
+        ),
+    )));
+
+    // Lens with a quarter wave magnesium fluoride coating, reflecting less and tinted purple
+    world.add(Box::new(Sphere::new(
+        Point3::new(-0.3, 0.6, 0.0),
+        0.6,
+        Rc::new(Dielectric::new(1.5).with_thin_film(ThinFilm::new(100.0, 1.38))),
+    )));
+
+    // Oil on polished steel
+    world.add(Box::new(Sphere::new(
+        Point3::new(0.9, 0.4, 0.4),
+        0.4,
+        Rc::new(Conductor::chrome(0.05).with_thin_film(ThinFilm::new(450.0, 1.47))),
+    )));
+
+    // Tempered, oxidized metal
+    world.add(Box::new(Sphere::new(
+        Point3::new(1.9, 0.4, 0.0),
+        0.4,
+        Rc::new(
+            Metal::new(Color::new(0.6, 0.6, 0.6), 0.1).with_thin_film(ThinFilm::new(250.0, 2.4)),
+        ),
+    )));
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 30.0,
+        look_from: Point3::new(0.0, 2.0, 8.0),
+        look_at: Point3::new(0.0, 0.6, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 1.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
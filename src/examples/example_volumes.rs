@@ -0,0 +1,105 @@
+use std::rc::Rc;
+
+use crate::geometry::{make_box, Background, ConstantMedium, Quad, Sphere, World};
+use crate::materials::{Dielectric, DiffuseLight, HenyeyGreenstein, Lambertian};
+use crate::optics::{LensConfig, ViewportConfig};
+use crate::utils::{Color, Point3, Vec3};
+
+pub fn example_volumes() -> (World, ViewportConfig, LensConfig) {
+    // Materials
+    let red = Rc::new(Lambertian::from_albedo(Color::new(0.65, 0.05, 0.05)));
+    let white = Rc::new(Lambertian::from_albedo(Color::new(0.73, 0.73, 0.73)));
+    let green = Rc::new(Lambertian::from_albedo(Color::new(0.12, 0.45, 0.15)));
+    let light = Rc::new(DiffuseLight::from_color(Color::new(7.0, 7.0, 7.0)));
+    let glass = Rc::new(Dielectric::new(1.5));
+    let haze = Rc::new(HenyeyGreenstein::from_albedo(
+        Color::new(0.9, 0.9, 0.9),
+        0.6,
+    ));
+
+    // Cornell Box
+    let mut world = World::new(vec![
+        Box::new(Quad::new(
+            Point3::new(555.0, 0.0, 0.0),
+            Vec3::new(0.0, 555.0, 0.0),
+            Vec3::new(0.0, 0.0, 555.0),
+            green,
+        )),
+        Box::new(Quad::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 555.0, 0.0),
+            Vec3::new(0.0, 0.0, 555.0),
+            red,
+        )),
+        Box::new(Quad::new(
+            Point3::new(113.0, 554.0, 127.0),
+            Vec3::new(330.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 305.0),
+            light,
+        )),
+        Box::new(Quad::new(
+            Point3::new(0.0, 555.0, 0.0),
+            Vec3::new(555.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 555.0),
+            white.clone(),
+        )),
+        Box::new(Quad::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(555.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 555.0),
+            white.clone(),
+        )),
+        Box::new(Quad::new(
+            Point3::new(0.0, 0.0, 555.0),
+            Vec3::new(555.0, 0.0, 0.0),
+            Vec3::new(0.0, 555.0, 0.0),
+            white.clone(),
+        )),
+    ]);
+    world.background = Background::Solid(Color::zeros());
+
+    // Smoke and Fog Boxes
+    let tall_box = make_box(
+        &Point3::new(265.0, 0.0, 295.0),
+        &Point3::new(430.0, 330.0, 460.0),
+        white.clone(),
+    );
+    let short_box = make_box(
+        &Point3::new(130.0, 0.0, 65.0),
+        &Point3::new(295.0, 165.0, 230.0),
+        white,
+    );
+    world.add(Box::new(ConstantMedium::new(
+        Box::new(tall_box),
+        0.01,
+        Color::zeros(),
+    )));
+    world.add(Box::new(ConstantMedium::new(
+        Box::new(short_box),
+        0.01,
+        Color::new(1.0, 1.0, 1.0),
+    )));
+
+    // Glass Sphere filled with forward scattering haze
+    let center = Point3::new(400.0, 90.0, 150.0);
+    world.add(Box::new(Sphere::new(center, 70.0, glass)));
+    world.add(Box::new(ConstantMedium::from_phase(
+        Box::new(Sphere::new(center, 69.0, Rc::new(Lambertian::default()))),
+        0.02,
+        haze,
+    )));
+
+    let viewport_config = ViewportConfig {
+        vertical_fov: 40.0,
+        look_from: Point3::new(278.0, 278.0, -800.0),
+        look_at: Point3::new(278.0, 278.0, 0.0),
+        view_up: Vec3::new(0.0, 1.0, 0.0),
+    };
+
+    let lens_config = LensConfig {
+        depth_of_field_angle: 0.0,
+        focus_dist: 10.0,
+    };
+
+    (world, viewport_config, lens_config)
+}
@@ -1,19 +1,53 @@
 pub mod example;
+pub mod example_brushed_metal;
+pub mod example_bump;
+pub mod example_clouds;
+pub mod example_coated;
+pub mod example_cutout;
+pub mod example_emissive_mesh;
 // mod example_frost;
+pub mod example_god_rays;
+pub mod example_ies;
+pub mod example_metals;
+pub mod example_mix;
 // mod example_motion_blur;
+pub mod example_nested_dielectrics;
 // mod example_normal;
 // mod example_performance;
 pub mod example_perlin;
+pub mod example_principled;
+pub mod example_prism;
 pub mod example_quads;
+pub mod example_rough_diffuse;
+pub mod example_rough_glass;
 pub mod example_suzanne;
+pub mod example_thin_film;
 // mod example_textures;
+pub mod example_volumes;
 
 pub use example::*;
+pub use example_brushed_metal::*;
+pub use example_bump::*;
+pub use example_clouds::*;
+pub use example_coated::*;
+pub use example_cutout::*;
+pub use example_emissive_mesh::*;
 // pub use example_frost::*;
+pub use example_god_rays::*;
+pub use example_ies::*;
+pub use example_metals::*;
+pub use example_mix::*;
 // pub use example_motion_blur::*;
+pub use example_nested_dielectrics::*;
 // pub use example_normal::*;
 // pub use example_performance::*;
 pub use example_perlin::*;
+pub use example_principled::*;
+pub use example_prism::*;
 pub use example_quads::*;
+pub use example_rough_diffuse::*;
+pub use example_rough_glass::*;
 pub use example_suzanne::*;
+pub use example_thin_film::*;
 // pub use example_textures::*;
+pub use example_volumes::*;
@@ -4,6 +4,7 @@ use crate::geometry::{HitRecord, Hittable};
 use crate::optics::Ray;
 use crate::utils::{Interval, Point3, Vec3Ext};
 
+#[derive(Debug, Clone, Copy)]
 pub enum Axis {
     X,
     Y,
@@ -186,8 +187,14 @@ impl AABB {
     }
 
     pub fn hit(&self, ray: &Ray, t: Interval, rec: &mut HitRecord) -> bool {
-        rec.debug.inc_intersection_checks();
+        rec.debug.inc_traversal_steps();
 
+        // Leaves `rec.t` alone, the caller may already hold a closer hit
+        self.overlap(ray, t).is_some()
+    }
+
+    // Parameter range of the ray inside the box, if it meets `t`
+    pub fn overlap(&self, ray: &Ray, t: Interval) -> Option<Interval> {
         let interval_x = self.x();
         let interval_y = self.y();
         let interval_z = self.z();
@@ -209,10 +216,9 @@ impl AABB {
         let t_max = t_x0.max(t_x1).min(t_y0.max(t_y1).min(t_z0.max(t_z1)));
 
         if t_min > t_max || t_min > t.max || t_max < t.min {
-            return false;
+            return None;
         }
 
-        rec.t = t_min;
-        true
+        Some(Interval::new(t_min, t_max))
     }
 }
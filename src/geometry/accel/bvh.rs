@@ -1,9 +1,11 @@
 use crate::geometry::{accel::AABB, Axis, HitRecord, Hittable, HittableList};
+use crate::lights::Light;
 use crate::optics::Ray;
 use crate::utils::Interval;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::fmt::Debug;
+use std::rc::Rc;
 
 const CUTOFF: usize = 4;
 const MAX_DEPTH: u32 = 16;
@@ -192,21 +194,14 @@ impl Hittable for Bvh {
                     }
                 }
                 BvhNode::Inner(_, left_idx, right_idx) => {
-                    let mut rec_left = rec.clone();
-                    let mut rec_right = rec.clone();
+                    let left = self.tree[*left_idx].bounding_box().overlap(r, t_closest);
+                    let right = self.tree[*right_idx].bounding_box().overlap(r, t_closest);
+                    let (left_hit, right_hit) = (left.is_some(), right.is_some());
 
-                    let left_hit =
-                        self.tree[*left_idx]
-                            .bounding_box()
-                            .hit(r, t_closest, &mut rec_left);
+                    rec.debug.traversal_steps += 2;
 
-                    let right_hit =
-                        self.tree[*right_idx]
-                            .bounding_box()
-                            .hit(r, t_closest, &mut rec_right);
-
-                    if left_hit && right_hit {
-                        let (first_idx, second_idx) = if rec_left.t < rec_right.t {
+                    if let (Some(left), Some(right)) = (left, right) {
+                        let (first_idx, second_idx) = if left.min < right.min {
                             (right_idx, left_idx)
                         } else {
                             (left_idx, right_idx)
@@ -233,4 +228,41 @@ impl Hittable for Bvh {
     fn clone_box(&self) -> Box<dyn Hittable> {
         Box::new(self.clone())
     }
+
+    // Every leaf along the ray contributes, not just the closest one
+    fn transmittance(&self, r: &Ray, t: Interval) -> f64 {
+        let mut transmittance = 1.0;
+        let mut stack = vec![0];
+
+        while let Some(i) = stack.pop() {
+            if self.tree[i].bounding_box().overlap(r, t).is_none() {
+                continue;
+            }
+
+            match &self.tree[i] {
+                BvhNode::Leaf(leaf) => {
+                    transmittance *= leaf.transmittance(r, t);
+                    if transmittance <= 0.0 {
+                        return 0.0;
+                    }
+                }
+                BvhNode::Inner(_, left_idx, right_idx) => {
+                    stack.push(*left_idx);
+                    stack.push(*right_idx);
+                }
+            }
+        }
+
+        transmittance
+    }
+
+    fn lights(&self) -> Vec<Rc<dyn Light>> {
+        self.tree
+            .iter()
+            .flat_map(|node| match node {
+                BvhNode::Leaf(leaf) => leaf.lights(),
+                BvhNode::Inner(_, _, _) => vec![],
+            })
+            .collect()
+    }
 }
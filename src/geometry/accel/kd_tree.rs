@@ -0,0 +1,92 @@
+use crate::geometry::accel::{Axis, AABB};
+use crate::utils::Point3;
+
+// Balanced kd-tree over points, stored implicitly with each node at the median of its range
+#[derive(Debug, Clone)]
+pub struct KdTree<T> {
+    items: Vec<(Point3, T)>,
+    axes: Vec<Axis>,
+}
+
+impl<T> KdTree<T> {
+    pub fn new(items: Vec<(Point3, T)>) -> Self {
+        let mut items = items;
+        let mut axes = vec![Axis::X; items.len()];
+        Self::build(&mut items, &mut axes);
+
+        Self { items, axes }
+    }
+
+    fn build(items: &mut [(Point3, T)], axes: &mut [Axis]) {
+        if items.len() <= 1 {
+            return;
+        }
+
+        let bbox = items.iter().fold(AABB::empty(), |bbox, (p, _)| {
+            AABB::wrap_boxes(&bbox, &AABB::wrap_points(p, p))
+        });
+        let axis = bbox.longest_axis();
+        let idx = axis.idx();
+
+        let mid = items.len() / 2;
+        items.select_nth_unstable_by(mid, |a, b| a.0[idx].total_cmp(&b.0[idx]));
+        axes[mid] = axis;
+
+        let (left_items, right_items) = items.split_at_mut(mid);
+        let (left_axes, right_axes) = axes.split_at_mut(mid);
+        Self::build(left_items, left_axes);
+        Self::build(&mut right_items[1..], &mut right_axes[1..]);
+    }
+}
+
+impl<T> KdTree<T> {
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn items(&self) -> &[(Point3, T)] {
+        &self.items
+    }
+
+    // Calls `f` for every item within `radius` of `p`
+    pub fn for_each_within<F: FnMut(&Point3, &T)>(&self, p: &Point3, radius: f64, mut f: F) {
+        self.visit(0, self.items.len(), p, radius * radius, &mut f);
+    }
+
+    fn visit<F: FnMut(&Point3, &T)>(
+        &self,
+        lo: usize,
+        hi: usize,
+        p: &Point3,
+        radius_squared: f64,
+        f: &mut F,
+    ) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let (q, item) = &self.items[mid];
+        let idx = self.axes[mid].idx();
+        let delta = p[idx] - q[idx];
+
+        // Nearer side first, the farther side only if the splitting plane is within range
+        let (near, far) = if delta < 0.0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+
+        self.visit(near.0, near.1, p, radius_squared, f);
+        if (q - p).norm_squared() <= radius_squared {
+            f(q, item);
+        }
+        if delta * delta <= radius_squared {
+            self.visit(far.0, far.1, p, radius_squared, f);
+        }
+    }
+}
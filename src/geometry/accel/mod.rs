@@ -1,5 +1,7 @@
 pub mod aabb;
 pub mod bvh;
+pub mod kd_tree;
 
 pub use aabb::*;
 pub use bvh::*;
+pub use kd_tree::*;
@@ -0,0 +1,114 @@
+use rand::Rng;
+use std::rc::Rc;
+
+use crate::geometry::{HitRecord, RaySampler};
+use crate::materials::{HenyeyGreenstein, Material};
+use crate::optics::Ray;
+use crate::utils::{Color, Interval, Vec3, Vec3Ext};
+
+// There is a single atmosphere per world, so a fixed salt tells it apart from other media
+const SALT: u64 = 0x61746d6f;
+
+// Scene wide participating medium around the camera, thinning out exponentially with height
+#[derive(Debug, Clone)]
+pub struct Atmosphere {
+    extinction: f64, // At the base height
+    base_height: f64,
+    height_falloff: f64, // Zero for homogeneous fog
+    phase: Rc<dyn Material>,
+}
+
+impl Atmosphere {
+    pub fn new(scattering: f64, absorption: f64, anisotropy: f64) -> Self {
+        Self::with_height_falloff(scattering, absorption, anisotropy, 0.0, 0.0)
+    }
+
+    // Coefficients are given at `base_height`, the density drops by 1/e every 1/`falloff` above it
+    pub fn with_height_falloff(
+        scattering: f64,
+        absorption: f64,
+        anisotropy: f64,
+        base_height: f64,
+        height_falloff: f64,
+    ) -> Self {
+        let extinction = scattering + absorption;
+        let albedo = if extinction > 0.0 {
+            scattering / extinction
+        } else {
+            0.0
+        };
+
+        Self {
+            extinction,
+            base_height,
+            height_falloff,
+            phase: Rc::new(HenyeyGreenstein::from_albedo(
+                Color::constant(albedo),
+                anisotropy,
+            )),
+        }
+    }
+}
+
+impl Atmosphere {
+    // Extinction at the start of the ray and its exponential rate of change along the ray
+    fn profile(&self, r: &Ray, t_start: f64) -> (f64, f64) {
+        let height = r.at(t_start).y() - self.base_height;
+        let extinction = self.extinction * (-self.height_falloff * height).exp();
+        (extinction, self.height_falloff * r.direction().y())
+    }
+
+    fn optical_depth(&self, r: &Ray, t: Interval) -> f64 {
+        let (extinction, rate) = self.profile(r, t.min);
+        let length = t.size();
+        if rate.abs() < 1e-9 {
+            extinction * length
+        } else {
+            extinction * (1.0 - (-rate * length).exp()) / rate
+        }
+    }
+
+    pub fn transmittance(&self, r: &Ray, t: Interval) -> f64 {
+        if self.extinction <= 0.0 || t.size() <= 0.0 {
+            return 1.0;
+        }
+        (-self.optical_depth(r, t)).exp()
+    }
+
+    // Samples a collision before `t.max` by inverting the optical depth, fills `rec` if there is one
+    pub fn hit(&self, r: &Ray, t: Interval, rec: &mut HitRecord) -> bool {
+        if self.extinction <= 0.0 || t.size() <= 0.0 {
+            return false;
+        }
+
+        let (extinction, rate) = self.profile(r, t.min);
+        let mut rng = RaySampler::new(r, SALT);
+        let depth = -(1.0 - rng.gen::<f64>()).ln();
+        let distance = if rate.abs() < 1e-9 {
+            depth / extinction
+        } else {
+            // Rays climbing out of thin fog may never collide
+            let remaining = 1.0 - depth * rate / extinction;
+            if remaining <= 0.0 {
+                return false;
+            }
+            -remaining.ln() / rate
+        };
+
+        let t_hit = t.min + distance;
+        if !t.surrounds(t_hit) {
+            return false;
+        }
+
+        rec.t = t_hit;
+        rec.p = r.at(t_hit);
+        rec.normal = Vec3::zeros(); // Scattering inside the volume, not on a surface
+        rec.tangent = Vec3::zeros();
+        rec.front_face = true;
+        rec.mat = Rc::clone(&self.phase);
+        rec.light = None;
+        (rec.u, rec.v) = (0.0, 0.0);
+
+        true
+    }
+}
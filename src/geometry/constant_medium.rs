@@ -0,0 +1,115 @@
+use rand::Rng;
+use std::rc::Rc;
+
+use crate::geometry::{accel::AABB, HitRecord, Hittable, RaySampler};
+use crate::materials::{Isotropic, Material};
+use crate::optics::Ray;
+use crate::textures::Texture;
+use crate::utils::{Color, Interval, Vec3};
+
+// Volume of constant density inside a closed boundary, scattering according to a phase function
+#[derive(Debug, Clone)]
+pub struct ConstantMedium {
+    boundary: Box<dyn Hittable>,
+    neg_inv_density: f64,
+    phase_function: Rc<dyn Material>,
+    salt: u64,
+}
+
+impl ConstantMedium {
+    pub fn new(boundary: Box<dyn Hittable>, density: f64, albedo: Color) -> Self {
+        Self::from_phase(boundary, density, Rc::new(Isotropic::from_albedo(albedo)))
+    }
+
+    pub fn from_texture(
+        boundary: Box<dyn Hittable>,
+        density: f64,
+        texture: Rc<dyn Texture>,
+    ) -> Self {
+        Self::from_phase(boundary, density, Rc::new(Isotropic::from_texture(texture)))
+    }
+
+    pub fn from_phase(
+        boundary: Box<dyn Hittable>,
+        density: f64,
+        phase_function: Rc<dyn Material>,
+    ) -> Self {
+        let salt = RaySampler::bounds_salt(boundary.bounding_box());
+        Self {
+            boundary,
+            neg_inv_density: -1.0 / density,
+            phase_function,
+            salt,
+        }
+    }
+}
+
+impl Hittable for ConstantMedium {
+    fn hit(&self, r: &Ray, t: Interval, rec: &mut HitRecord) -> bool {
+        // Entry and exit of the boundary along the whole line, the ray may start inside
+        let mut rec1 = HitRecord::default();
+        if !self.boundary.hit(r, Interval::universe(), &mut rec1) {
+            return false;
+        }
+
+        let mut rec2 = HitRecord::default();
+        if !self
+            .boundary
+            .hit(r, Interval::right_open(rec1.t + 0.0001), &mut rec2)
+        {
+            return false;
+        }
+
+        let t_enter = rec1.t.max(t.min).max(0.0);
+        let t_exit = rec2.t.min(t.max);
+        if t_enter >= t_exit {
+            return false;
+        }
+
+        // Free flight distance, note that the direction vector is normalized
+        let distance_inside = t_exit - t_enter;
+        let mut rng = RaySampler::new(r, self.salt);
+        let hit_distance = self.neg_inv_density * rng.gen::<f64>().ln();
+        if hit_distance > distance_inside {
+            return false;
+        }
+
+        rec.t = t_enter + hit_distance;
+        rec.p = r.at(rec.t);
+        rec.normal = Vec3::zeros(); // Scattering inside the volume, not on a surface
+        rec.tangent = Vec3::zeros();
+        rec.front_face = true;
+        rec.mat = Rc::clone(&self.phase_function);
+        rec.light = None;
+        (rec.u, rec.v) = (0.0, 0.0);
+
+        true
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        self.boundary.bounding_box()
+    }
+
+    fn clone_box(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+
+    // Beer-Lambert along the part of the ray inside the boundary
+    fn transmittance(&self, r: &Ray, t: Interval) -> f64 {
+        let mut rec1 = HitRecord::default();
+        if !self.boundary.hit(r, Interval::universe(), &mut rec1) {
+            return 1.0;
+        }
+
+        let mut rec2 = HitRecord::default();
+        if !self
+            .boundary
+            .hit(r, Interval::right_open(rec1.t + 0.0001), &mut rec2)
+        {
+            return 1.0;
+        }
+
+        let distance_inside = (rec2.t.min(t.max) - rec1.t.max(t.min)).max(0.0);
+        (distance_inside / self.neg_inv_density).exp()
+    }
+}
@@ -0,0 +1,209 @@
+use rand::Rng;
+use std::rc::Rc;
+
+use crate::geometry::{accel::AABB, HitRecord, Hittable, RaySampler, Voxel, VoxelGrid};
+use crate::materials::{HenyeyGreenstein, Material};
+use crate::optics::Ray;
+use crate::utils::{Color, ColorExt, Interval, Point3, Sampler, Vec3, Vec3Ext};
+
+#[derive(Debug, Clone)]
+pub struct MediumConfig {
+    pub density: f64, // Extinction coefficient of a voxel with unit density
+    pub albedo: Color,
+    pub anisotropy: f64,          // Henyey-Greenstein g
+    pub emission: Color,          // Radiance of a voxel with unit emission
+    pub blackbody_intensity: f64, // Radiance of a voxel at 1000K, grows with the fourth power
+}
+
+impl Default for MediumConfig {
+    fn default() -> Self {
+        Self {
+            density: 1.0,
+            albedo: Color::ones(),
+            anisotropy: 0.0,
+            emission: Color::zeros(),
+            blackbody_intensity: 0.0,
+        }
+    }
+}
+
+// Phase function inside the grid, emitting where the voxels are hot or glowing
+#[derive(Debug)]
+struct GridInterior {
+    grid: Rc<VoxelGrid>,
+    bbox: AABB,
+    phase: HenyeyGreenstein,
+    config: MediumConfig,
+}
+
+impl GridInterior {
+    fn voxel(&self, p: &Point3) -> Voxel {
+        let extent = self.bbox.max - self.bbox.min;
+        self.grid
+            .sample(&(p - self.bbox.min).component_div(&extent))
+    }
+
+    fn radiance(&self, voxel: &Voxel) -> Color {
+        let mut radiance = self.config.emission * voxel.emission;
+        if voxel.temperature > 0.0 && self.config.blackbody_intensity > 0.0 {
+            radiance += Color::blackbody(voxel.temperature)
+                * self.config.blackbody_intensity
+                * (voxel.temperature / 1000.0).powi(4);
+        }
+        radiance
+    }
+}
+
+impl Material for GridInterior {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        rng: &mut Sampler,
+        attenuation: &mut Vec3,
+        scattered: &mut Ray,
+    ) -> bool {
+        self.phase.scatter(r_in, rec, rng, attenuation, scattered)
+    }
+
+    fn is_emissive(&self) -> bool {
+        !self.config.emission.near_zero() || self.config.blackbody_intensity > 0.0
+    }
+
+    fn is_specular(&self) -> bool {
+        false
+    }
+
+    // Collisions absorb with probability 1 - albedo, which is where the medium emits
+    fn emitted(&self, _: &Ray, rec: &HitRecord) -> Color {
+        if !self.is_emissive() {
+            return Color::zeros();
+        }
+        let absorption = (Color::ones() - self.config.albedo).map(|a| a.max(0.0));
+        self.radiance(&self.voxel(&rec.p))
+            .component_mul(&absorption)
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> Color {
+        self.phase.eval(r_in, rec, direction)
+    }
+
+    fn pdf(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> f64 {
+        self.phase.pdf(r_in, rec, direction)
+    }
+}
+
+// Voxel grid stretched over a box, free flights are sampled with delta tracking against the
+// densest voxel and shadow rays are attenuated with ratio tracking
+// Separate random streams for collisions and shadow rays, so the two estimates are uncorrelated
+const COLLISION_STREAM: u64 = 0;
+const TRANSMITTANCE_STREAM: u64 = 1;
+
+#[derive(Debug, Clone)]
+pub struct HeterogeneousMedium {
+    interior: Rc<GridInterior>,
+    majorant: f64,
+    salt: u64,
+}
+
+impl HeterogeneousMedium {
+    pub fn new(grid: Rc<VoxelGrid>, min: Point3, max: Point3, config: MediumConfig) -> Self {
+        let majorant = grid.max_density() * config.density;
+        let phase = HenyeyGreenstein::from_albedo(config.albedo, config.anisotropy);
+        let bbox = AABB::wrap_points(&min, &max);
+
+        Self {
+            salt: RaySampler::bounds_salt(&bbox),
+            interior: Rc::new(GridInterior {
+                grid,
+                bbox,
+                phase,
+                config,
+            }),
+            majorant,
+        }
+    }
+}
+
+impl HeterogeneousMedium {
+    fn density(&self, p: &Point3) -> f64 {
+        self.interior.voxel(p).density * self.interior.config.density
+    }
+
+    // Part of the ray inside the box, intersected with `t`
+    fn segment(&self, r: &Ray, t: Interval) -> Option<Interval> {
+        if self.majorant <= 0.0 {
+            return None;
+        }
+        let overlap = self.interior.bbox.overlap(r, t)?;
+        let segment = Interval::new(overlap.min.max(t.min), overlap.max.min(t.max));
+        (segment.size() > 0.0).then_some(segment)
+    }
+}
+
+impl Hittable for HeterogeneousMedium {
+    fn hit(&self, r: &Ray, t: Interval, rec: &mut HitRecord) -> bool {
+        rec.debug.inc_traversal_steps();
+        let Some(segment) = self.segment(r, t) else {
+            return false;
+        };
+
+        // Tentative collisions against the majorant, real with probability density / majorant
+        let mut rng = RaySampler::new(r, (self.salt, COLLISION_STREAM));
+        let mut t_hit = segment.min;
+        loop {
+            t_hit -= (1.0 - rng.gen::<f64>()).ln() / self.majorant;
+            if t_hit >= segment.max {
+                return false;
+            }
+            if rng.gen::<f64>() * self.majorant < self.density(&r.at(t_hit)) {
+                break;
+            }
+        }
+
+        rec.t = t_hit;
+        rec.p = r.at(t_hit);
+        rec.normal = Vec3::zeros(); // Scattering inside the volume, not on a surface
+        rec.tangent = Vec3::zeros();
+        rec.front_face = true;
+        rec.mat = self.interior.clone();
+        rec.light = None;
+        (rec.u, rec.v) = (0.0, 0.0);
+
+        true
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.interior.bbox
+    }
+
+    fn clone_box(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+
+    fn transmittance(&self, r: &Ray, t: Interval) -> f64 {
+        let Some(segment) = self.segment(r, t) else {
+            return 1.0;
+        };
+
+        // Ratio tracking, each tentative collision keeps the null collision probability
+        let mut rng = RaySampler::new(r, (self.salt, TRANSMITTANCE_STREAM));
+        let mut transmittance = 1.0;
+        let mut t_step = segment.min;
+        loop {
+            t_step -= (1.0 - rng.gen::<f64>()).ln() / self.majorant;
+            if t_step >= segment.max {
+                return transmittance;
+            }
+            transmittance *= 1.0 - self.density(&r.at(t_step)) / self.majorant;
+
+            // Russian roulette once little light is left
+            if transmittance < 0.1 {
+                if rng.gen::<f64>() < 0.5 {
+                    return 0.0;
+                }
+                transmittance *= 2.0;
+            }
+        }
+    }
+}
@@ -1,17 +1,30 @@
 use std::rc::Rc;
 
+use crate::lights::Light;
 use crate::materials::{Lambertian, Material};
 use crate::optics::Ray;
-use crate::utils::Vec3;
+use crate::utils::{Onb, Vec3};
 
 #[derive(Clone, Default)]
 pub struct HitRecordDebug {
-    pub intersection_checks: u32,
+    pub traversal_steps: u32,
+    pub primitive_tests: u32,
+    pub barycentrics: Vec3,
 }
 
 impl HitRecordDebug {
-    pub fn inc_intersection_checks(&mut self) {
-        self.intersection_checks += 1;
+    // Bounding box tests
+    pub fn inc_traversal_steps(&mut self) {
+        self.traversal_steps += 1;
+    }
+
+    // Exact tests against the geometry itself
+    pub fn inc_primitive_tests(&mut self) {
+        self.primitive_tests += 1;
+    }
+
+    pub fn intersection_checks(&self) -> u32 {
+        self.traversal_steps + self.primitive_tests
     }
 }
 
@@ -19,7 +32,10 @@ impl HitRecordDebug {
 pub struct HitRecord {
     pub p: Vec3,
     pub normal: Vec3,
+    pub tangent: Vec3, // Along increasing u, not normalized nor orthogonal, zero where unknown
+    pub bitangent: Vec3, // Along increasing v, likewise
     pub mat: Rc<dyn Material>,
+    pub light: Option<Rc<dyn Light>>,
     pub t: f64,
     pub u: f64,
     pub v: f64,
@@ -32,10 +48,13 @@ impl Default for HitRecord {
         Self {
             p: Vec3::zeros(),
             normal: Vec3::zeros(),
+            tangent: Vec3::zeros(),
+            bitangent: Vec3::zeros(),
             t: 0.0,
             u: 0.0,
             v: 0.0,
             mat: Rc::new(Lambertian::from_albedo(Vec3::zeros())),
+            light: None,
             front_face: false,
             debug: HitRecordDebug::default(),
         }
@@ -43,6 +62,11 @@ impl Default for HitRecord {
 }
 
 impl HitRecord {
+    // Local frame around the normal with its first axis along the tangent where there is one
+    pub fn shading_frame(&self) -> Onb {
+        Onb::from_wu(&self.normal, &self.tangent)
+    }
+
     pub fn set_face_normal(&mut self, r: &Ray, outward_normal: Vec3) {
         self.front_face = r.direction().dot(&outward_normal) < 0.0;
         self.normal = if self.front_face {
@@ -1,8 +1,14 @@
+use rand::{Rng, RngCore};
+use std::collections::hash_map::DefaultHasher;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
 
 use crate::geometry::{accel::AABB, HitRecord};
+use crate::lights::Light;
+use crate::materials::Material;
 use crate::optics::Ray;
-use crate::utils::Interval;
+use crate::utils::{fill_bytes_via_u64, Interval, Point3, Vec3Ext};
 
 pub trait Hittable: Debug {
     fn hit(&self, r: &Ray, t: Interval, rec: &mut HitRecord) -> bool;
@@ -10,6 +16,21 @@ pub trait Hittable: Debug {
     fn bounding_box(&self) -> &AABB;
 
     fn clone_box(&self) -> Box<dyn Hittable>;
+
+    // Fraction of light passing along `r` within `t`, surfaces block it entirely
+    fn transmittance(&self, r: &Ray, t: Interval) -> f64 {
+        let mut rec = HitRecord::default();
+        if self.hit(r, t, &mut rec) {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    // Emitters that should be sampled explicitly, collected by the World
+    fn lights(&self) -> Vec<Rc<dyn Light>> {
+        vec![]
+    }
 }
 
 impl Clone for Box<dyn Hittable> {
@@ -17,3 +38,73 @@ impl Clone for Box<dyn Hittable> {
         self.clone_box()
     }
 }
+
+// Whether the opacity of `mat` lets `r` through at `p`, randomly but always alike for the same ray
+pub fn cut_out(mat: &Rc<dyn Material>, r: &Ray, u: f64, v: f64, p: &Point3) -> bool {
+    let opacity = mat.opacity(u, v, p);
+    if opacity >= 1.0 {
+        return false;
+    }
+    if opacity <= 0.0 {
+        return true;
+    }
+
+    // Salted with the hit point, so that each surface along the ray decides on its own
+    let salt = [p.x().to_bits(), p.y().to_bits(), p.z().to_bits()];
+    RaySampler::new(r, salt).gen::<f64>() >= opacity
+}
+
+// Random numbers for sampling along a ray, seeded by the ray itself and `salt` instead of drawn
+// from the path's sampler. The ray follows from the primary samples, so a path that Metropolis
+// restores replays the same collisions.
+#[derive(Debug, Clone)]
+pub struct RaySampler {
+    state: u64,
+}
+
+impl RaySampler {
+    pub fn new(r: &Ray, salt: impl Hash) -> Self {
+        let mut hasher = DefaultHasher::new();
+        for x in r.origin().iter().chain(r.direction().iter()) {
+            x.to_bits().hash(&mut hasher);
+        }
+        r.time().to_bits().hash(&mut hasher);
+        salt.hash(&mut hasher);
+        Self {
+            state: hasher.finish(),
+        }
+    }
+
+    // Salt telling apart objects by where they are, unlike an address it is the same on every run
+    pub fn bounds_salt(bbox: &AABB) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for x in bbox.min.iter().chain(bbox.max.iter()) {
+            x.to_bits().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+impl RngCore for RaySampler {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    // SplitMix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_via_u64(self, dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
@@ -1,11 +1,11 @@
 use std::fmt::Debug;
+use std::rc::Rc;
 
 use crate::geometry::{accel::AABB, HitRecord, Hittable};
+use crate::lights::Light;
 use crate::optics::Ray;
 use crate::utils::Interval;
 
-pub type World = HittableList;
-
 #[derive(Debug, Clone)]
 pub struct HittableList {
     pub objects: Vec<Box<dyn Hittable>>,
@@ -61,4 +61,27 @@ impl Hittable for HittableList {
     fn clone_box(&self) -> Box<dyn Hittable> {
         Box::new(self.clone())
     }
+
+    fn transmittance(&self, r: &Ray, t: Interval) -> f64 {
+        if self.bbox.overlap(r, t).is_none() {
+            return 1.0;
+        }
+
+        let mut transmittance = 1.0;
+        for object in self.objects.iter() {
+            transmittance *= object.transmittance(r, t);
+            if transmittance <= 0.0 {
+                break;
+            }
+        }
+
+        transmittance
+    }
+
+    fn lights(&self) -> Vec<Rc<dyn Light>> {
+        self.objects
+            .iter()
+            .flat_map(|object| object.lights())
+            .collect()
+    }
 }
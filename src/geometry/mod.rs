@@ -1,16 +1,26 @@
 pub mod accel;
+pub mod atmosphere;
+pub mod constant_medium;
+pub mod heterogeneous_medium;
 pub mod hit_record;
 pub mod hittable;
 pub mod hittable_list;
 pub mod quad;
 pub mod sphere;
 pub mod triangle_mesh;
+pub mod voxel_grid;
+pub mod world;
 
 // Export
 pub use accel::*;
+pub use atmosphere::*;
+pub use constant_medium::*;
+pub use heterogeneous_medium::*;
 pub use hit_record::*;
 pub use hittable::*;
 pub use hittable_list::*;
 pub use quad::*;
 pub use sphere::*;
 pub use triangle_mesh::*;
+pub use voxel_grid::*;
+pub use world::*;
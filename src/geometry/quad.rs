@@ -1,7 +1,8 @@
-use crate::geometry::{accel::AABB, HitRecord, Hittable};
+use crate::geometry::{accel::AABB, cut_out, HitRecord, Hittable, HittableList};
+use crate::lights::{Light, TriangleLight};
 use crate::materials::Material;
 use crate::optics::Ray;
-use crate::utils::{Interval, Point3, Vec3};
+use crate::utils::{Interval, Point3, Vec3, Vec3Ext};
 use std::rc::Rc;
 
 #[derive(Debug, Clone)]
@@ -14,6 +15,7 @@ pub struct Quad {
     d: f64,
     mat: Rc<dyn Material>,
     bbox: AABB,
+    lights: Vec<Rc<dyn Light>>,
 }
 
 impl Quad {
@@ -27,6 +29,28 @@ impl Quad {
         let bbox_diag2 = AABB::wrap_points(&(q + u), &(q + v));
         let bbox = AABB::wrap_boxes(&bbox_diag1, &bbox_diag2);
 
+        // An emissive quad is sampled as two area lights split along the q+u, q+v diagonal
+        let lights = if mat.is_emissive() {
+            let normals = [normal; 3];
+            let halves = [
+                ([q, q + u, q + v], [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]),
+                (
+                    [q + u + v, q + v, q + u],
+                    [(1.0, 1.0), (0.0, 1.0), (1.0, 0.0)],
+                ),
+            ];
+            halves
+                .into_iter()
+                .map(|(vertices, uvs)| {
+                    let uvs = uvs.map(|(s, t)| Point3::new(s, t, 0.0));
+                    Rc::new(TriangleLight::new(vertices, normals, uvs, mat.clone()))
+                        as Rc<dyn Light>
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
         Self {
             q,
             u,
@@ -36,12 +60,15 @@ impl Quad {
             d,
             mat,
             bbox,
+            lights,
         }
     }
 }
 
 impl Hittable for Quad {
     fn hit(&self, r: &Ray, t: Interval, rec: &mut HitRecord) -> bool {
+        rec.debug.inc_primitive_tests();
+
         let denom = self.normal.dot(&r.direction());
         if denom.abs() < 1e-8 {
             return false;
@@ -62,11 +89,22 @@ impl Hittable for Quad {
             return false;
         }
 
+        if cut_out(&self.mat, r, alpha, beta, &p) {
+            return false;
+        }
+
         rec.t = t_int;
         rec.p = p;
         rec.mat = Rc::clone(&self.mat);
+        rec.light = match self.lights.as_slice() {
+            [first, second] => Some(Rc::clone(if alpha + beta <= 1.0 { first } else { second })),
+            _ => None,
+        };
+        rec.debug.barycentrics = Vec3::zeros();
         rec.set_face_normal(r, self.normal);
         (rec.u, rec.v) = (alpha, beta);
+        rec.tangent = self.u;
+        rec.bitangent = self.v;
 
         true
     }
@@ -78,4 +116,34 @@ impl Hittable for Quad {
     fn clone_box(&self) -> Box<dyn Hittable> {
         Box::new(self.clone())
     }
+
+    fn lights(&self) -> Vec<Rc<dyn Light>> {
+        self.lights.clone()
+    }
+}
+
+// Axis aligned box with opposite corners `a` and `b`, made of six quads
+pub fn make_box(a: &Point3, b: &Point3, mat: Rc<dyn Material>) -> HittableList {
+    let min = a.inf(b);
+    let max = a.sup(b);
+
+    let dx = Vec3::new(max.x() - min.x(), 0.0, 0.0);
+    let dy = Vec3::new(0.0, max.y() - min.y(), 0.0);
+    let dz = Vec3::new(0.0, 0.0, max.z() - min.z());
+
+    let sides = [
+        (Point3::new(min.x(), min.y(), max.z()), dx, dy), // Front
+        (Point3::new(max.x(), min.y(), max.z()), -dz, dy), // Right
+        (Point3::new(max.x(), min.y(), min.z()), -dx, dy), // Back
+        (Point3::new(min.x(), min.y(), min.z()), dz, dy), // Left
+        (Point3::new(min.x(), max.y(), max.z()), dx, -dz), // Top
+        (Point3::new(min.x(), min.y(), min.z()), dx, dz), // Bottom
+    ];
+
+    HittableList::new(
+        sides
+            .into_iter()
+            .map(|(q, u, v)| Box::new(Quad::new(q, u, v, mat.clone())) as Box<dyn Hittable>)
+            .collect(),
+    )
 }
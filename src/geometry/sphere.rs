@@ -69,6 +69,17 @@ impl Sphere {
 
         (u, v)
     }
+
+    // Derivatives of the position with respect to u and v, zero along the poles where v is singular
+    fn derivatives(&self, p: &Point3) -> (Vec3, Vec3) {
+        let dp_du = 2.0 * PI * self.radius * Vec3::new(p.z(), 0.0, -p.x());
+        let rho = (p.x() * p.x() + p.z() * p.z()).sqrt();
+        if rho < 1e-9 {
+            return (dp_du, Vec3::zeros());
+        }
+        let dp_dv = PI * self.radius * Vec3::new(-p.x() * p.y() / rho, rho, -p.y() * p.z() / rho);
+        (dp_du, dp_dv)
+    }
 }
 
 impl Hittable for Sphere {
@@ -77,7 +88,7 @@ impl Hittable for Sphere {
             return false;
         }
 
-        rec.debug.inc_intersection_checks();
+        rec.debug.inc_primitive_tests();
 
         let center = self.center(r.time());
         let oc = r.origin() - center;
@@ -103,9 +114,12 @@ impl Hittable for Sphere {
         rec.t = root;
         rec.p = r.at(rec.t);
         rec.mat = Rc::clone(&self.mat);
+        rec.light = None;
+        rec.debug.barycentrics = Vec3::zeros();
         let outward_normal = (rec.p - center) / self.radius;
         rec.set_face_normal(r, outward_normal);
         (rec.u, rec.v) = self.uv(&outward_normal);
+        (rec.tangent, rec.bitangent) = self.derivatives(&outward_normal);
 
         true
     }
@@ -1,4 +1,5 @@
-use crate::geometry::{accel::AABB, Bvh, HitRecord, Hittable};
+use crate::geometry::{accel::AABB, cut_out, Bvh, HitRecord, Hittable};
+use crate::lights::{Light, TriangleLight};
 use crate::materials::Material;
 use crate::optics::Ray;
 use crate::utils::{Interval, Point3, Vec3, Vec3Ext};
@@ -11,8 +12,11 @@ pub struct Triangle {
     vertices: [Point3; 3],
     normals: [Vec3; 3],
     uvs: [Point3; 3],
+    tangent: Vec3,   // Derivative of the position with respect to u
+    bitangent: Vec3, // And with respect to v
 
     mat: Rc<dyn Material>,
+    light: Option<Rc<TriangleLight>>,
     bbox: AABB,
 }
 
@@ -25,11 +29,27 @@ impl Triangle {
     ) -> Self {
         let bbox = AABB::wrap_triangle(&vertices);
 
+        // Solve the edges for the derivatives, along the first two edges where the UVs are degenerate
+        let (e1, e2) = (vertices[1] - vertices[0], vertices[2] - vertices[0]);
+        let (duv1, duv2) = (uvs[1] - uvs[0], uvs[2] - uvs[0]);
+        let det = duv1.x() * duv2.y() - duv2.x() * duv1.y();
+        let (tangent, bitangent) = if det.abs() < 1e-12 {
+            (e1, e2)
+        } else {
+            (
+                (e1 * duv2.y() - e2 * duv1.y()) / det,
+                (e2 * duv1.x() - e1 * duv2.x()) / det,
+            )
+        };
+
         Self {
             vertices,
             normals,
             uvs,
+            tangent,
+            bitangent,
             mat,
+            light: None,
             bbox,
         }
     }
@@ -47,7 +67,7 @@ impl Hittable for Triangle {
             return false;
         }
 
-        rec.debug.inc_intersection_checks();
+        rec.debug.inc_primitive_tests();
 
         let e1 = self.vertices[1] - self.vertices[0];
         let e2 = self.vertices[2] - self.vertices[0];
@@ -81,14 +101,24 @@ impl Hittable for Triangle {
             return false;
         }
 
+        let p = r.at(root);
+        let tex_u = self.uvs[0].x() * (1.0 - u - v) + self.uvs[1].x() * u + self.uvs[2].x() * v;
+        let tex_v = self.uvs[0].y() * (1.0 - u - v) + self.uvs[1].y() * u + self.uvs[2].y() * v;
+        if cut_out(&self.mat, r, tex_u, tex_v, &p) {
+            return false;
+        }
+
         rec.t = root;
-        rec.p = r.at(rec.t);
+        rec.p = p;
         let normal = (self.normals[0] * (1.0 - u - v) + self.normals[1] * u + self.normals[2] * v)
             .normalize();
         rec.set_face_normal(r, normal);
-        rec.u = self.uvs[0].x() * (1.0 - u - v) + self.uvs[1].x() * u + self.uvs[2].x() * v;
-        rec.v = self.uvs[0].y() * (1.0 - u - v) + self.uvs[1].y() * u + self.uvs[2].y() * v;
+        rec.tangent = self.tangent;
+        rec.bitangent = self.bitangent;
+        (rec.u, rec.v) = (tex_u, tex_v);
         rec.mat = Rc::clone(&self.mat);
+        rec.light = self.light.clone().map(|light| light as Rc<dyn Light>);
+        rec.debug.barycentrics = Vec3::new(1.0 - u - v, u, v);
 
         true
     }
@@ -105,6 +135,7 @@ impl Hittable for Triangle {
 #[derive(Debug, Clone)]
 pub struct TriangleMesh {
     bvh: Bvh,
+    lights: Vec<Rc<dyn Light>>,
 }
 
 impl TriangleMesh {
@@ -115,6 +146,7 @@ impl TriangleMesh {
         let mut normals = Vec::new();
         let mut uvs = Vec::new();
         let mut faces = Vec::new();
+        let mut lights: Vec<Rc<dyn Light>> = Vec::new();
 
         for line in obj_file.lines() {
             let mut parts = line.split_whitespace();
@@ -151,8 +183,17 @@ impl TriangleMesh {
                         _normals[i] = normals[indices[2] - 1];
                         _uvs[i] = uvs[indices[1] - 1];
                     }
-                    let face = Triangle::new(_vertices, _normals, _uvs, mat.clone()).into();
-                    faces.push(face);
+                    let mut face = Triangle::new(_vertices, _normals, _uvs, mat.clone());
+
+                    // Every face of an emissive mesh is an area light
+                    if mat.is_emissive() {
+                        let light =
+                            Rc::new(TriangleLight::new(_vertices, _normals, _uvs, mat.clone()));
+                        lights.push(light.clone());
+                        face.light = Some(light);
+                    }
+
+                    faces.push(face.into());
                 }
                 _ => {}
             }
@@ -165,7 +206,7 @@ impl TriangleMesh {
             before_bvh.elapsed().as_millis()
         );
 
-        Self { bvh }
+        Self { bvh, lights }
     }
 }
 
@@ -182,4 +223,8 @@ impl Hittable for TriangleMesh {
     fn clone_box(&self) -> Box<dyn Hittable> {
         Box::new(self.clone())
     }
+
+    fn lights(&self) -> Vec<Rc<dyn Light>> {
+        self.lights.clone()
+    }
 }
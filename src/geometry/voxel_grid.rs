@@ -0,0 +1,204 @@
+use std::path::Path;
+
+use crate::utils::{Perlin, Point3, Vec3Ext};
+
+// Magic bytes of the binary format, followed by the header and data of the ASCII format as
+// little endian u32 and f32 values
+const BINARY_MAGIC: &[u8; 4] = b"VXG1";
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Voxel {
+    pub density: f64,
+    pub temperature: f64, // Kelvin, emits as a blackbody if positive
+    pub emission: f64,
+}
+
+impl Voxel {
+    fn lerp(&self, other: &Voxel, t: f64) -> Voxel {
+        Voxel {
+            density: self.density + (other.density - self.density) * t,
+            temperature: self.temperature + (other.temperature - self.temperature) * t,
+            emission: self.emission + (other.emission - self.emission) * t,
+        }
+    }
+}
+
+// Voxels covering the unit cube, x varies fastest, then y, then z
+//
+// Grid files start with `nx ny nz channels`, then list the values of every voxel with the channels
+// (density, temperature, emission) interleaved. Missing channels are zero, lines starting with
+// '#' are comments.
+#[derive(Debug, Clone)]
+pub struct VoxelGrid {
+    resolution: [usize; 3],
+    voxels: Vec<Voxel>,
+    max_density: f64,
+}
+
+impl VoxelGrid {
+    pub fn new(resolution: [usize; 3], voxels: Vec<Voxel>) -> Self {
+        assert_eq!(
+            voxels.len(),
+            resolution[0] * resolution[1] * resolution[2],
+            "Voxel count does not match the resolution"
+        );
+        let max_density = voxels.iter().map(|v| v.density).fold(0.0, f64::max);
+
+        Self {
+            resolution,
+            voxels,
+            max_density,
+        }
+    }
+
+    // Evaluates `f` at the voxel centers, in unit cube coordinates
+    pub fn from_fn(resolution: [usize; 3], f: impl Fn(&Point3) -> Voxel) -> Self {
+        let [nx, ny, nz] = resolution;
+        let mut voxels = Vec::with_capacity(nx * ny * nz);
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    let p = Point3::new(
+                        (i as f64 + 0.5) / nx as f64,
+                        (j as f64 + 0.5) / ny as f64,
+                        (k as f64 + 0.5) / nz as f64,
+                    );
+                    voxels.push(f(&p));
+                }
+            }
+        }
+
+        Self::new(resolution, voxels)
+    }
+
+    // Cloud-like turbulence, fading out towards the boundary of the inscribed sphere
+    pub fn from_perlin(resolution: [usize; 3], scale: f64) -> Self {
+        let perlin = Perlin::default();
+        Self::from_fn(resolution, |p| {
+            let falloff = (1.0 - 2.0 * (p - Point3::constant(0.5)).norm()).max(0.0);
+            let noise = perlin.turb(&(p * scale), 7);
+            Voxel {
+                density: (noise * 2.0 * falloff + falloff - 0.3).max(0.0),
+                ..Voxel::default()
+            }
+        })
+    }
+
+    // Reads an ASCII or binary grid file, errors name the file and what is wrong with it
+    pub fn load(path: &str) -> Result<Self, String> {
+        let bytes = std::fs::read(Path::new(path)).map_err(|e| format!("{path}: {e}"))?;
+        let grid = if let Some(data) = bytes.strip_prefix(BINARY_MAGIC) {
+            Self::parse_binary(data)
+        } else {
+            String::from_utf8(bytes)
+                .map_err(|_| "not a binary grid and not valid UTF-8 text".to_string())
+                .and_then(|text| Self::parse_ascii(&text))
+        };
+        grid.map_err(|e| format!("{path}: {e}"))
+    }
+
+    fn parse_ascii(text: &str) -> Result<Self, String> {
+        let mut values = text
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(|line| line.split_whitespace());
+
+        let mut header = || {
+            let value = values.next().ok_or("header is incomplete")?;
+            value
+                .parse::<usize>()
+                .map_err(|_| format!("invalid header value '{value}'"))
+        };
+        let resolution = [header()?, header()?, header()?];
+        let channels = header()?;
+
+        let data = values
+            .map(|v| v.parse().map_err(|_| format!("invalid voxel value '{v}'")))
+            .collect::<Result<Vec<f64>, String>>()?;
+        Self::from_channels(resolution, channels, &data)
+    }
+
+    fn parse_binary(bytes: &[u8]) -> Result<Self, String> {
+        if !bytes.len().is_multiple_of(4) {
+            return Err("binary data is not a whole number of 4 byte words".to_string());
+        }
+        let mut words = bytes
+            .chunks_exact(4)
+            .map(|chunk| [chunk[0], chunk[1], chunk[2], chunk[3]]);
+
+        let mut header = || {
+            let word = words.next().ok_or("header is incomplete")?;
+            Ok::<usize, String>(u32::from_le_bytes(word) as usize)
+        };
+        let resolution = [header()?, header()?, header()?];
+        let channels = header()?;
+
+        let data: Vec<f64> = words.map(|w| f32::from_le_bytes(w) as f64).collect();
+        Self::from_channels(resolution, channels, &data)
+    }
+
+    fn from_channels(
+        resolution: [usize; 3],
+        channels: usize,
+        data: &[f64],
+    ) -> Result<Self, String> {
+        if !(1..=3).contains(&channels) {
+            return Err(format!(
+                "{channels} channels, voxel grids have one to three"
+            ));
+        }
+        if resolution.contains(&0) {
+            return Err(format!("empty resolution {resolution:?}"));
+        }
+        let expected = resolution
+            .iter()
+            .try_fold(channels, |acc, &n| acc.checked_mul(n))
+            .ok_or_else(|| format!("resolution {resolution:?} is too large"))?;
+        if data.len() != expected {
+            return Err(format!(
+                "{} values for resolution {resolution:?} with {channels} channels, expected {expected}",
+                data.len()
+            ));
+        }
+
+        let voxels = data
+            .chunks_exact(channels)
+            .map(|values| Voxel {
+                density: values[0],
+                temperature: values.get(1).copied().unwrap_or(0.0),
+                emission: values.get(2).copied().unwrap_or(0.0),
+            })
+            .collect();
+
+        Ok(Self::new(resolution, voxels))
+    }
+}
+
+impl VoxelGrid {
+    pub fn max_density(&self) -> f64 {
+        self.max_density
+    }
+
+    // Trilinear interpolation between voxel centers, `p` in unit cube coordinates
+    pub fn sample(&self, p: &Point3) -> Voxel {
+        let mut base = [0; 3];
+        let mut frac = [0.0; 3];
+        for axis in 0..3 {
+            let n = self.resolution[axis];
+            let x = (p[axis] * n as f64 - 0.5).clamp(0.0, (n - 1) as f64);
+            base[axis] = (x as usize).min(n.saturating_sub(2));
+            frac[axis] = x - base[axis] as f64;
+        }
+
+        let voxel = |di: usize, dj: usize, dk: usize| {
+            let i = (base[0] + di).min(self.resolution[0] - 1);
+            let j = (base[1] + dj).min(self.resolution[1] - 1);
+            let k = (base[2] + dk).min(self.resolution[2] - 1);
+            self.voxels[(k * self.resolution[1] + j) * self.resolution[0] + i]
+        };
+
+        let lerp_x = |dj, dk| voxel(0, dj, dk).lerp(&voxel(1, dj, dk), frac[0]);
+        let lerp_y = |dk| lerp_x(0, dk).lerp(&lerp_x(1, dk), frac[1]);
+        lerp_y(0).lerp(&lerp_y(1), frac[2])
+    }
+}
@@ -0,0 +1,113 @@
+use std::rc::Rc;
+
+use crate::geometry::{accel::AABB, Atmosphere, HitRecord, Hittable, HittableList};
+use crate::lights::{Light, LightSampling};
+use crate::optics::Ray;
+use crate::utils::{Color, Interval, Point3, Vec3, Vec3Ext};
+
+#[derive(Debug, Clone)]
+pub enum Background {
+    Sky,
+    Solid(Color),
+}
+
+impl Background {
+    pub fn sample(&self, r: &Ray) -> Color {
+        match self {
+            Background::Sky => {
+                let t = 0.5 * (r.direction().y() + 1.0);
+                Color::new(1.0, 1.0, 1.0) * (1.0 - t) + Color::new(0.5, 0.7, 1.0) * t
+            }
+            Background::Solid(color) => *color,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct World {
+    objects: HittableList,
+    pub lights: Vec<Rc<dyn Light>>,
+    pub background: Background,
+    pub light_sampling: LightSampling,
+    pub atmosphere: Option<Atmosphere>,
+}
+
+impl World {
+    pub fn new(objects: Vec<Box<dyn Hittable>>) -> Self {
+        let objects = HittableList::new(objects);
+        Self {
+            lights: objects.lights(),
+            objects,
+            background: Background::Sky,
+            light_sampling: LightSampling::default(),
+            atmosphere: None,
+        }
+    }
+
+    pub fn add(&mut self, object: Box<dyn Hittable>) {
+        self.lights.extend(object.lights());
+        self.objects.add(object);
+    }
+
+    pub fn add_light(&mut self, light: Rc<dyn Light>) {
+        self.lights.push(light);
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+
+impl World {
+    // Fog along the way blocks the shadow ray with probability one minus its transmittance
+    pub fn visible(&self, p: &Point3, direction: &Vec3, distance: f64, time: f64) -> bool {
+        let shadow_ray = Ray::new(*p, *direction, time);
+        let mut rec = HitRecord::default();
+        !self.hit(
+            &shadow_ray,
+            Interval::new(0.001, distance - 0.001),
+            &mut rec,
+        )
+    }
+
+    // Like `visible`, but volumes only attenuate instead of blocking, and so does the medium the
+    // shadow ray travels through
+    pub fn transmittance_towards(&self, shadow_ray: &Ray, distance: f64) -> Color {
+        let volumes = self.transmittance(shadow_ray, Interval::new(0.001, distance - 0.001));
+        shadow_ray.transmittance(distance) * volumes
+    }
+}
+
+impl Hittable for World {
+    // Surfaces first, then the atmosphere in front of the closest one
+    fn hit(&self, r: &Ray, t: Interval, rec: &mut HitRecord) -> bool {
+        let hit_surface = self.objects.hit(r, t, rec);
+
+        if let Some(atmosphere) = &self.atmosphere {
+            let t_max = if hit_surface { rec.t } else { t.max };
+            if atmosphere.hit(r, Interval::new(t.min, t_max), rec) {
+                return true;
+            }
+        }
+
+        hit_surface
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        self.objects.bounding_box()
+    }
+
+    fn clone_box(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+
+    fn transmittance(&self, r: &Ray, t: Interval) -> f64 {
+        let fog = match &self.atmosphere {
+            Some(atmosphere) => atmosphere.transmittance(r, t),
+            None => 1.0,
+        };
+        fog * self.objects.transmittance(r, t)
+    }
+}
@@ -0,0 +1,36 @@
+use crate::geometry::{HitRecord, Hittable, World};
+use crate::integrators::Integrator;
+use crate::optics::Ray;
+use crate::utils::{Color, Interval, Sampler, Vec3, Vec3Ext};
+
+#[derive(Debug)]
+pub struct AmbientOcclusion {
+    max_distance: f64,
+}
+
+impl AmbientOcclusion {
+    pub fn new(max_distance: f64) -> Self {
+        Self { max_distance }
+    }
+}
+
+impl Integrator for AmbientOcclusion {
+    fn ray_color(&self, r: &Ray, world: &World, rng: &mut Sampler) -> Color {
+        let mut rec = HitRecord::default();
+        if !world.hit(r, Interval::right_open(0.001), &mut rec) {
+            return Color::ones();
+        }
+
+        // Cosine weighted, so the unoccluded fraction is the estimate
+        let mut direction = rec.normal + Vec3::random_unit_sphere_vector(rng);
+        if direction.near_zero() {
+            direction = rec.normal;
+        }
+
+        if world.visible(&rec.p, &direction.normalize(), self.max_distance, r.time()) {
+            Color::ones()
+        } else {
+            Color::zeros()
+        }
+    }
+}
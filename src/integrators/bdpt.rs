@@ -0,0 +1,608 @@
+use image::Rgb32FImage;
+use rand::prelude::Rng;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::geometry::{HitRecord, Hittable, World};
+use crate::integrators::{Integrator, SceneLights};
+use crate::lights::{Light, LightSampling};
+use crate::optics::{NestedMedium, Projection, Ray};
+use crate::utils::{Color, ColorExt, Interval, Point3, Sampler, Vec3, Vec3Ext};
+
+#[derive(Clone)]
+enum VertexKind {
+    Camera,
+    Light(Rc<dyn Light>),
+    Surface(HitRecord),
+}
+
+// Path vertex, densities are per unit area in both directions along the path
+#[derive(Clone)]
+struct Vertex {
+    kind: VertexKind,
+    p: Point3,
+    normal: Vec3,             // Zero for vertices not on a surface
+    wi: Vec3,                 // Direction of the arriving ray
+    media: Vec<NestedMedium>, // Those the arriving ray travelled through
+    beta: Color,
+    delta: bool,
+    pdf_fwd: f64,
+    pdf_rev: f64,
+}
+
+impl Vertex {
+    fn endpoint(kind: VertexKind, p: Point3, normal: Vec3, beta: Color, pdf_fwd: f64) -> Self {
+        Self {
+            kind,
+            p,
+            normal,
+            wi: Vec3::zeros(),
+            media: vec![],
+            beta,
+            delta: false,
+            pdf_fwd,
+            pdf_rev: 0.0,
+        }
+    }
+
+    fn is_on_surface(&self) -> bool {
+        !self.normal.near_zero()
+    }
+
+    fn is_connectible(&self) -> bool {
+        match &self.kind {
+            VertexKind::Surface(rec) => !rec.mat.is_specular(),
+            _ => true,
+        }
+    }
+
+    fn light(&self) -> Option<&Rc<dyn Light>> {
+        match &self.kind {
+            VertexKind::Light(light) => Some(light),
+            VertexKind::Surface(rec) => rec.light.as_ref(),
+            VertexKind::Camera => None,
+        }
+    }
+
+    fn arriving_ray(&self, time: f64) -> Ray {
+        Ray::new(self.p - self.wi, self.wi, time).with_media(self.media.clone())
+    }
+
+    // Material response towards `next`, including the cosine at this vertex
+    fn f(&self, next: &Vertex) -> Color {
+        match &self.kind {
+            VertexKind::Surface(rec) => {
+                let r_in = self.arriving_ray(0.0);
+                rec.mat.eval(&r_in, rec, &(next.p - self.p).normalize())
+            }
+            _ => Color::zeros(),
+        }
+    }
+
+    // Ray leaving towards `next`, in the media on that side of the surface
+    fn ray_towards(&self, next: &Vertex, time: f64) -> Ray {
+        let direction = (next.p - self.p).normalize();
+        let media = match &self.kind {
+            VertexKind::Surface(rec) => rec
+                .mat
+                .media_towards(&self.arriving_ray(time), rec, &direction)
+                .unwrap_or_else(|| self.media.clone()),
+            _ => self.media.clone(),
+        };
+        Ray::new(self.p, direction, time).with_media(media)
+    }
+
+    fn convert_density(&self, pdf: f64, next: &Vertex) -> f64 {
+        let w = next.p - self.p;
+        let distance_squared = w.norm_squared();
+        if distance_squared <= 0.0 {
+            return 0.0;
+        }
+
+        if next.is_on_surface() {
+            pdf * next.normal.dot(&w).abs() / distance_squared.powf(1.5)
+        } else {
+            pdf / distance_squared
+        }
+    }
+
+    // Density of continuing the path from this vertex to `next`, having arrived from `prev`
+    fn pdf(&self, prev: Option<&Vertex>, next: &Vertex, projection: &Projection) -> f64 {
+        match &self.kind {
+            VertexKind::Camera => {
+                self.convert_density(projection.pdf_direction(&self.p, &next.p), next)
+            }
+            VertexKind::Light(_) => self.pdf_light(next),
+            VertexKind::Surface(rec) => {
+                let Some(prev) = prev else {
+                    return 0.0;
+                };
+                let r_in = Ray::new(prev.p, self.p - prev.p, 0.0).with_media(self.media.clone());
+                let pdf = rec.mat.pdf(&r_in, rec, &(next.p - self.p).normalize());
+                self.convert_density(pdf, next)
+            }
+        }
+    }
+
+    // Density of a light path leaving this vertex towards `next`
+    fn pdf_light(&self, next: &Vertex) -> f64 {
+        let Some(light) = self.light() else {
+            return 0.0;
+        };
+        let (_, pdf_direction) = light.pdf_emission(&self.p, &(next.p - self.p).normalize());
+        self.convert_density(pdf_direction, next)
+    }
+
+    // Density of a light path starting at this vertex
+    fn pdf_light_origin(&self, next: &Vertex, lights: &SceneLights) -> f64 {
+        let Some(light) = self.light() else {
+            return 0.0;
+        };
+        let (pdf_position, _) = light.pdf_emission(&self.p, &(next.p - self.p).normalize());
+        lights.pmf(&self.p, &self.normal, light) * pdf_position
+    }
+}
+
+struct Subpaths {
+    camera: Vec<Vertex>,
+    light: Vec<Vertex>,
+    time: f64,
+}
+
+// Bidirectional path tracing, connecting every prefix of a camera path to every prefix of a light path
+#[derive(Debug)]
+pub struct Bdpt {
+    max_depth: u32,
+    lights: SceneLights,
+    projection: Projection,
+    splats: RefCell<Vec<Color>>, // Light paths reaching the camera through any pixel
+    camera_rays: Cell<u64>,
+}
+
+impl Bdpt {
+    pub fn new(max_depth: u32) -> Self {
+        Self {
+            max_depth,
+            lights: SceneLights::default(),
+            projection: Projection::default(),
+            splats: RefCell::new(vec![]),
+            camera_rays: Cell::new(0),
+        }
+    }
+}
+
+impl Bdpt {
+    // Emitters outside of the light list can only be found by camera paths
+    fn is_sampled_light(&self, rec: &HitRecord) -> bool {
+        rec.light
+            .as_ref()
+            .is_some_and(|light| self.lights.contains(light))
+    }
+
+    // Returns radiance that only the camera path can find, escaped rays and unregistered emitters
+    fn camera_subpath(
+        &self,
+        r: &Ray,
+        world: &World,
+        rng: &mut Sampler,
+        path: &mut Vec<Vertex>,
+    ) -> Color {
+        let camera = Vertex::endpoint(
+            VertexKind::Camera,
+            r.origin(),
+            Vec3::zeros(),
+            Color::ones(),
+            1.0,
+        );
+        let pdf_direction = self.projection.pdf_direction(&r.origin(), &r.at(1.0));
+        path.push(camera);
+
+        self.random_walk(r, Color::ones(), pdf_direction, world, rng, path)
+    }
+
+    fn light_subpath(&self, time: f64, world: &World, rng: &mut Sampler, path: &mut Vec<Vertex>) {
+        let Some((light_idx, pmf)) =
+            self.lights
+                .sample(&Point3::zeros(), &Vec3::zeros(), rng.gen())
+        else {
+            return;
+        };
+        let light = &world.lights[light_idx];
+        let Some(emission) = light.sample_emission(rng) else {
+            return;
+        };
+        if emission.pdf_position <= 0.0
+            || emission.pdf_direction <= 0.0
+            || emission.radiance.near_zero()
+        {
+            return;
+        }
+
+        let pdf_origin = pmf * emission.pdf_position;
+        path.push(Vertex::endpoint(
+            VertexKind::Light(Rc::clone(light)),
+            emission.ray.origin(),
+            emission.normal,
+            emission.radiance / pdf_origin,
+            pdf_origin,
+        ));
+
+        let cos_theta = if emission.normal.near_zero() {
+            1.0
+        } else {
+            emission.normal.dot(&emission.ray.direction()).abs()
+        };
+        let beta = emission.radiance * cos_theta / (pdf_origin * emission.pdf_direction);
+
+        let r = Ray::new(emission.ray.origin(), emission.ray.direction(), time);
+        self.random_walk(&r, beta, emission.pdf_direction, world, rng, path);
+    }
+
+    fn random_walk(
+        &self,
+        r: &Ray,
+        beta: Color,
+        pdf: f64,
+        world: &World,
+        rng: &mut Sampler,
+        path: &mut Vec<Vertex>,
+    ) -> Color {
+        // Camera paths get one more vertex, as they can end on a light by themselves
+        let from_camera = matches!(path[0].kind, VertexKind::Camera);
+        let max_vertices = self.max_depth as usize + if from_camera { 2 } else { 1 };
+
+        let mut unweighted = Color::zeros();
+        let mut ray = r.clone();
+        let mut beta = beta;
+        let mut pdf_fwd = pdf;
+
+        while path.len() < max_vertices {
+            let mut rec = HitRecord::default();
+            if !world.hit(&ray, Interval::right_open(0.001), &mut rec) {
+                if from_camera {
+                    unweighted += beta.component_mul(&world.background.sample(&ray));
+                }
+                break;
+            }
+
+            // Absorbed by the medium along the way
+            beta = beta.component_mul(&ray.transmittance(rec.t));
+
+            if from_camera && !self.is_sampled_light(&rec) {
+                unweighted += beta.component_mul(&rec.mat.emitted(&ray, &rec));
+            }
+
+            let mut vertex = Vertex {
+                kind: VertexKind::Surface(rec.clone()),
+                p: rec.p,
+                normal: rec.normal,
+                wi: ray.direction(),
+                media: ray.media().to_vec(),
+                beta,
+                delta: false,
+                pdf_fwd: 0.0,
+                pdf_rev: 0.0,
+            };
+            vertex.pdf_fwd = path.last().unwrap().convert_density(pdf_fwd, &vertex);
+            path.push(vertex);
+
+            if path.len() >= max_vertices {
+                break;
+            }
+
+            let mut scattered = Ray::new(Point3::zeros(), Vec3::zeros(), 0.0);
+            let mut attenuation = Color::zeros();
+            if !rec
+                .mat
+                .scatter(&ray, &rec, rng, &mut attenuation, &mut scattered)
+            {
+                break;
+            }
+
+            // Specular scattering is not sampled by a density, ratios treat it as one. So do smooth
+            // spots of rough materials.
+            pdf_fwd = if rec.mat.is_specular() {
+                0.0
+            } else {
+                rec.mat.pdf(&ray, &rec, &scattered.direction())
+            };
+            let pdf_rev = if pdf_fwd <= 0.0 {
+                path.last_mut().unwrap().delta = true;
+                pdf_fwd = 0.0;
+                0.0
+            } else {
+                let reversed = Ray::new(rec.p, -scattered.direction(), ray.time())
+                    .with_media(scattered.media().to_vec());
+                rec.mat.pdf(&reversed, &rec, &-ray.direction())
+            };
+
+            beta = beta.component_mul(&attenuation);
+            if beta.near_zero() {
+                break;
+            }
+
+            let n = path.len();
+            path[n - 2].pdf_rev = path[n - 1].convert_density(pdf_rev, &path[n - 2]);
+            ray = scattered;
+        }
+
+        unweighted
+    }
+}
+
+impl Bdpt {
+    fn connect(
+        &self,
+        paths: &Subpaths,
+        s: usize,
+        t: usize,
+        world: &World,
+        rng: &mut Sampler,
+    ) -> Color {
+        let time = paths.time;
+        let pt = &paths.camera[t - 1];
+        let mut sampled = None;
+
+        let l = if s == 0 {
+            // Camera path hit a light by itself
+            let VertexKind::Surface(rec) = &pt.kind else {
+                return Color::zeros();
+            };
+            if !self.is_sampled_light(rec) {
+                return Color::zeros();
+            }
+            let r_in = Ray::new(pt.p - pt.wi, pt.wi, time);
+            pt.beta.component_mul(&rec.mat.emitted(&r_in, rec))
+        } else if s == 1 {
+            // Fresh light sample, as in next event estimation
+            if !pt.is_connectible() {
+                return Color::zeros();
+            }
+            let Some((light_idx, pmf)) = self.lights.sample(&pt.p, &pt.normal, rng.gen()) else {
+                return Color::zeros();
+            };
+            let light = &world.lights[light_idx];
+            let Some(sample) = light.sample(&pt.p, rng) else {
+                return Color::zeros();
+            };
+            if sample.pdf <= 0.0 {
+                return Color::zeros();
+            }
+
+            let mut vertex = Vertex::endpoint(
+                VertexKind::Light(Rc::clone(light)),
+                pt.p + sample.direction * sample.distance,
+                sample.normal,
+                sample.radiance / (sample.pdf * pmf),
+                0.0,
+            );
+            vertex.pdf_fwd = vertex.pdf_light_origin(pt, &self.lights);
+
+            let l = pt
+                .beta
+                .component_mul(&pt.f(&vertex))
+                .component_mul(&vertex.beta)
+                .component_mul(&pt.ray_towards(&vertex, time).transmittance(sample.distance));
+            if l.near_zero() || !world.visible(&pt.p, &sample.direction, sample.distance, time) {
+                return Color::zeros();
+            }
+            sampled = Some(vertex);
+            l
+        } else {
+            let qs = &paths.light[s - 1];
+            if !qs.is_connectible() || !pt.is_connectible() {
+                return Color::zeros();
+            }
+
+            let d = pt.p - qs.p;
+            let distance = d.norm();
+            let l = qs
+                .beta
+                .component_mul(&qs.f(pt))
+                .component_mul(&pt.f(qs))
+                .component_mul(&pt.beta)
+                .component_mul(&pt.ray_towards(qs, time).transmittance(distance))
+                / (distance * distance);
+            if l.near_zero() || !world.visible(&qs.p, &(d / distance), distance, time) {
+                return Color::zeros();
+            }
+            l
+        };
+
+        l * self.mis_weight(paths, sampled.as_ref(), s, t)
+    }
+
+    // Light path vertex seen through the lens, returns the pixel and its contribution
+    fn connect_to_camera(
+        &self,
+        paths: &Subpaths,
+        s: usize,
+        world: &World,
+        rng: &mut Sampler,
+    ) -> Option<((u32, u32), Color)> {
+        let qs = &paths.light[s - 1];
+        if !qs.is_connectible() {
+            return None;
+        }
+
+        let p_lens = self.projection.sample_lens(rng);
+        let raster = self.projection.raster(&p_lens, &qs.p)?;
+        let pdf = self.projection.pdf_lens(&p_lens, &qs.p);
+        let importance = self.projection.importance(&p_lens, &qs.p);
+        if pdf <= 0.0 || importance <= 0.0 {
+            return None;
+        }
+
+        let camera = Vertex::endpoint(
+            VertexKind::Camera,
+            p_lens,
+            Vec3::zeros(),
+            Color::constant(importance / pdf),
+            0.0,
+        );
+
+        let to_lens = p_lens - qs.p;
+        let distance = to_lens.norm();
+        let l = qs
+            .beta
+            .component_mul(&qs.f(&camera))
+            .component_mul(&camera.beta)
+            .component_mul(&qs.ray_towards(&camera, paths.time).transmittance(distance));
+        if l.near_zero() || !world.visible(&qs.p, &(to_lens / distance), distance, paths.time) {
+            return None;
+        }
+
+        let weight = self.mis_weight(paths, Some(&camera), s, 1);
+        Some((raster, l * weight))
+    }
+
+    // Balance heuristic over all strategies that could have produced the same path
+    fn mis_weight(&self, paths: &Subpaths, sampled: Option<&Vertex>, s: usize, t: usize) -> f64 {
+        if s + t == 2 {
+            return 1.0;
+        }
+
+        // Connection endpoints, with a freshly sampled endpoint replacing the stored one
+        let qs = match (s, sampled) {
+            (0, _) => None,
+            (1, Some(vertex)) => Some(vertex),
+            _ => Some(&paths.light[s - 1]),
+        };
+        let pt = match (t, sampled) {
+            (1, Some(vertex)) => vertex,
+            _ => &paths.camera[t - 1],
+        };
+        let qs_minus = if s > 1 {
+            Some(&paths.light[s - 2])
+        } else {
+            None
+        };
+        let pt_minus = if t > 1 {
+            Some(&paths.camera[t - 2])
+        } else {
+            None
+        };
+
+        // Reverse densities across the connection
+        let pt_rev = match qs {
+            Some(qs) => qs.pdf(qs_minus, pt, &self.projection),
+            None => pt.pdf_light_origin(pt_minus.unwrap(), &self.lights),
+        };
+        let pt_minus_rev = pt_minus.map(|pt_minus| match qs {
+            Some(qs) => pt.pdf(Some(qs), pt_minus, &self.projection),
+            None => pt.pdf_light(pt_minus),
+        });
+        let qs_rev = qs.map(|qs| pt.pdf(pt_minus, qs, &self.projection));
+        let qs_minus_rev =
+            qs_minus.map(|qs_minus| qs.unwrap().pdf(Some(pt), qs_minus, &self.projection));
+
+        let remap = |pdf: f64| if pdf != 0.0 { pdf } else { 1.0 };
+        let mut sum_ri = 0.0;
+
+        let mut ri = 1.0;
+        for i in (1..t).rev() {
+            let (pdf_fwd, pdf_rev) = if i == t - 1 {
+                (pt.pdf_fwd, pt_rev)
+            } else if i == t - 2 {
+                (paths.camera[i].pdf_fwd, pt_minus_rev.unwrap())
+            } else {
+                (paths.camera[i].pdf_fwd, paths.camera[i].pdf_rev)
+            };
+            ri *= remap(pdf_rev) / remap(pdf_fwd);
+
+            let delta = i != t - 1 && paths.camera[i].delta;
+            if !delta && !paths.camera[i - 1].delta {
+                sum_ri += ri;
+            }
+        }
+
+        let mut ri = 1.0;
+        for i in (0..s).rev() {
+            let vertex = if i == s - 1 {
+                qs.unwrap()
+            } else {
+                &paths.light[i]
+            };
+            let pdf_rev = if i == s - 1 {
+                qs_rev.unwrap()
+            } else if i == s - 2 {
+                qs_minus_rev.unwrap()
+            } else {
+                vertex.pdf_rev
+            };
+            ri *= remap(pdf_rev) / remap(vertex.pdf_fwd);
+
+            let delta = i != s - 1 && vertex.delta;
+            let delta_light = if i > 0 {
+                paths.light[i - 1].delta
+            } else {
+                vertex.light().is_some_and(|light| light.is_delta())
+            };
+            if !delta && !delta_light {
+                sum_ri += ri;
+            }
+        }
+
+        1.0 / (1.0 + sum_ri)
+    }
+}
+
+impl Integrator for Bdpt {
+    fn prepare(&mut self, world: &World, projection: &Projection) {
+        // Light paths start independently of any receiver, so lights are picked by power alone
+        self.lights = SceneLights::from_sampling(world, LightSampling::Power);
+        self.projection = projection.clone();
+
+        let num_pixels = (projection.image_width() * projection.image_height()) as usize;
+        self.splats = RefCell::new(vec![Color::zeros(); num_pixels]);
+        self.camera_rays.set(0);
+    }
+
+    fn ray_color(&self, r: &Ray, world: &World, rng: &mut Sampler) -> Color {
+        self.camera_rays.set(self.camera_rays.get() + 1);
+
+        let mut paths = Subpaths {
+            camera: Vec::with_capacity(self.max_depth as usize + 2),
+            light: Vec::with_capacity(self.max_depth as usize + 1),
+            time: r.time(),
+        };
+        let mut color = self.camera_subpath(r, world, rng, &mut paths.camera);
+        self.light_subpath(r.time(), world, rng, &mut paths.light);
+
+        for t in 1..=paths.camera.len() {
+            // Light sampling for s = 1 does not need a light path
+            for s in 0..=paths.light.len().max(1) {
+                if s + t < 2 || s + t - 2 > self.max_depth as usize || (s == 1 && t == 1) {
+                    continue;
+                }
+
+                if t == 1 {
+                    let Some(((x, y), l)) = self.connect_to_camera(&paths, s, world, rng) else {
+                        continue;
+                    };
+                    let idx = (y * self.projection.image_width() + x) as usize;
+                    self.splats.borrow_mut()[idx] += l;
+                } else {
+                    color += self.connect(&paths, s, t, world, rng);
+                }
+            }
+        }
+
+        color
+    }
+
+    fn finish(&self, film: &mut Rgb32FImage) {
+        let num_pixels = (film.width() * film.height()) as f64;
+        let samples_per_pixel = self.camera_rays.get() as f64 / num_pixels;
+        if samples_per_pixel <= 0.0 {
+            return;
+        }
+
+        let width = film.width();
+        let splats = self.splats.borrow();
+        for (x, y, pixel) in film.enumerate_pixels_mut() {
+            let splat = splats[(y * width + x) as usize];
+            *pixel = (Color::from_rgb32f(pixel) + splat / samples_per_pixel).to_rgb32f();
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::geometry::{HitRecord, Hittable, World};
+use crate::integrators::Integrator;
+use crate::optics::Ray;
+use crate::utils::{Color, Interval, Sampler, Vec3Ext};
+
+#[derive(Debug, Clone, Copy)]
+pub enum DebugMode {
+    Normals,
+    Tangents,
+    UV,
+    Depth,
+    Barycentrics,
+}
+
+// Visualizes a property of the first hit, black where nothing is hit
+#[derive(Debug)]
+pub struct DebugIntegrator {
+    mode: DebugMode,
+}
+
+impl DebugIntegrator {
+    pub fn new(mode: DebugMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl Integrator for DebugIntegrator {
+    fn ray_color(&self, r: &Ray, world: &World, _: &mut Sampler) -> Color {
+        let mut rec = HitRecord::default();
+        if !world.hit(r, Interval::right_open(0.001), &mut rec) {
+            return Color::zeros();
+        }
+
+        match self.mode {
+            DebugMode::Normals => 0.5 * (rec.normal + Color::ones()),
+            DebugMode::Tangents => 0.5 * (rec.shading_frame().u() + Color::ones()),
+            DebugMode::UV => Color::new(rec.u, rec.v, 0.0),
+            DebugMode::Depth => Color::constant(1.0 / (1.0 + rec.t)),
+            DebugMode::Barycentrics => rec.debug.barycentrics,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::geometry::{HitRecord, Hittable, World};
+use crate::integrators::{Integrator, SceneLights};
+use crate::optics::{Projection, Ray};
+use crate::utils::{Color, Interval, Point3, Sampler, Vec3};
+
+// Single bounce lighting, only specular surfaces are followed further
+#[derive(Debug)]
+pub struct DirectLighting {
+    max_depth: u32,
+    lights: SceneLights,
+}
+
+impl DirectLighting {
+    pub fn new(max_depth: u32) -> Self {
+        Self {
+            max_depth,
+            lights: SceneLights::default(),
+        }
+    }
+}
+
+impl DirectLighting {
+    fn trace(&self, r: &Ray, depth: u32, world: &World, rng: &mut Sampler) -> Color {
+        if depth == 0 {
+            return Color::zeros();
+        }
+
+        let mut rec = HitRecord::default();
+        if !world.hit(r, Interval::right_open(0.001), &mut rec) {
+            return world.background.sample(r);
+        }
+
+        // Absorbed by the medium along the way
+        let absorbed = r.transmittance(rec.t);
+        let emitted = rec.mat.emitted(r, &rec);
+
+        if !rec.mat.is_specular() {
+            let direct = self.lights.sample_direct(r, &rec, world, rng, false);
+            return absorbed.component_mul(&(emitted + direct));
+        }
+
+        let mut scattered = Ray::new(Point3::zeros(), Vec3::zeros(), 0.0);
+        let mut attenuation = Color::zeros();
+        if rec
+            .mat
+            .scatter(r, &rec, rng, &mut attenuation, &mut scattered)
+        {
+            let incoming = self.trace(&scattered, depth - 1, world, rng);
+            return absorbed.component_mul(&(emitted + attenuation.component_mul(&incoming)));
+        }
+
+        absorbed.component_mul(&emitted)
+    }
+}
+
+impl Integrator for DirectLighting {
+    fn prepare(&mut self, world: &World, _: &Projection) {
+        self.lights = SceneLights::new(world);
+    }
+
+    fn ray_color(&self, r: &Ray, world: &World, rng: &mut Sampler) -> Color {
+        self.trace(r, self.max_depth, world, rng)
+    }
+}
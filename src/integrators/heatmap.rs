@@ -0,0 +1,243 @@
+use image::Rgb32FImage;
+use std::cell::Cell;
+use std::sync::Mutex;
+
+use crate::geometry::{HitRecord, HitRecordDebug, Hittable, World};
+use crate::integrators::Integrator;
+use crate::optics::{Projection, Ray};
+use crate::utils::{Color, ColorExt, Interval, Sampler, Vec3Ext};
+
+#[derive(Debug, Clone, Copy)]
+pub enum HeatmapMetric {
+    TraversalSteps,
+    PrimitiveTests,
+    Total,
+}
+
+impl HeatmapMetric {
+    pub fn count(&self, debug: &HitRecordDebug) -> u32 {
+        match self {
+            HeatmapMetric::TraversalSteps => debug.traversal_steps,
+            HeatmapMetric::PrimitiveTests => debug.primitive_tests,
+            HeatmapMetric::Total => debug.intersection_checks(),
+        }
+    }
+
+    // Count mapped to the hot end of the color scale
+    pub fn default_max(&self) -> u32 {
+        match self {
+            HeatmapMetric::TraversalSteps => 128,
+            HeatmapMetric::PrimitiveTests => 32,
+            HeatmapMetric::Total => 160,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct HeatmapStats {
+    min: u32,
+    max: u32,
+    sum: u64,
+    rays: u64,
+}
+
+impl Default for HeatmapStats {
+    fn default() -> Self {
+        Self {
+            min: u32::MAX,
+            max: 0,
+            sum: 0,
+            rays: 0,
+        }
+    }
+}
+
+impl HeatmapStats {
+    fn add(&mut self, count: u32) {
+        self.min = self.min.min(count);
+        self.max = self.max.max(count);
+        self.sum += count as u64;
+        self.rays += 1;
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
+        self.rays += other.rays;
+    }
+}
+
+// Statistics of every render finished since the last report, renders may run on several threads
+static TOTALS: Mutex<Option<(HeatmapMetric, u32, HeatmapStats)>> = Mutex::new(None);
+
+// False color view of the work done per camera ray, to spot bad acceleration structure splits
+#[derive(Debug)]
+pub struct Heatmap {
+    metric: HeatmapMetric,
+    max_count: u32,
+    stats: Cell<HeatmapStats>,
+}
+
+impl Heatmap {
+    pub fn new(metric: HeatmapMetric, max_count: u32) -> Self {
+        Self {
+            metric,
+            max_count: max_count.max(1),
+            stats: Cell::new(HeatmapStats::default()),
+        }
+    }
+
+    // Prints the statistics gathered by the renders finished so far once, then resets them
+    pub fn report() {
+        let Some((metric, max_count, stats)) = TOTALS.lock().unwrap().take() else {
+            return;
+        };
+        if stats.rays > 0 {
+            println!(
+                "{:?} per camera ray: min {}, max {}, avg {:.2} (color scale 0 to {})",
+                metric,
+                stats.min,
+                stats.max,
+                stats.sum as f64 / stats.rays as f64,
+                max_count
+            );
+        }
+    }
+}
+
+impl Integrator for Heatmap {
+    fn prepare(&mut self, _: &World, _: &Projection) {
+        self.stats.set(HeatmapStats::default());
+    }
+
+    fn ray_color(&self, r: &Ray, world: &World, _: &mut Sampler) -> Color {
+        let mut rec = HitRecord::default();
+        world.hit(r, Interval::right_open(0.001), &mut rec);
+
+        let count = self.metric.count(&rec.debug);
+        let mut stats = self.stats.get();
+        stats.add(count);
+        self.stats.set(stats);
+
+        // Rendered images are gamma corrected when aggregated
+        heat_color(count as f64 / self.max_count as f64).gamma_to_linear()
+    }
+
+    fn finish(&self, film: &mut Rgb32FImage) {
+        TOTALS
+            .lock()
+            .unwrap()
+            .get_or_insert((self.metric, self.max_count, HeatmapStats::default()))
+            .2
+            .merge(&self.stats.get());
+
+        draw_legend(film, self.max_count);
+    }
+}
+
+// Blue for little work, through cyan, yellow and red to dark red at `t >= 1`
+fn heat_color(t: f64) -> Color {
+    let stops = [
+        (0.0, Color::new(0.0, 0.0, 0.5)),
+        (0.125, Color::new(0.0, 0.0, 1.0)),
+        (0.375, Color::new(0.0, 1.0, 1.0)),
+        (0.625, Color::new(1.0, 1.0, 0.0)),
+        (0.875, Color::new(1.0, 0.0, 0.0)),
+        (1.0, Color::new(0.5, 0.0, 0.0)),
+    ];
+
+    let t = t.clamp(0.0, 1.0);
+    let i = stops
+        .iter()
+        .position(|&(s, _)| s >= t)
+        .unwrap_or(stops.len() - 1)
+        .max(1);
+    let ((s0, c0), (s1, c1)) = (stops[i - 1], stops[i]);
+    c0.lerp(&c1, (t - s0) / (s1 - s0))
+}
+
+// 3x5 pixel digits, one row per entry
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+// Color bar in the bottom left corner, labelled with the counts at both ends
+fn draw_legend(image: &mut Rgb32FImage, max_count: u32) {
+    let scale = (image.height() / 120).max(1);
+    let bar_width = image.width() / 3;
+    let bar_height = 6 * scale;
+    let margin = 4 * scale;
+    let legend_height = bar_height + 8 * scale;
+
+    if bar_width < 16 * scale || image.height() < legend_height + 2 * margin {
+        return;
+    }
+
+    let x0 = margin;
+    let y0 = image.height() - margin - legend_height;
+
+    let background = Color::zeros().to_rgb32f();
+    for y in y0 - scale..y0 + legend_height + scale {
+        for x in x0 - scale..x0 + bar_width + scale {
+            image.put_pixel(x, y, background);
+        }
+    }
+
+    for x in 0..bar_width {
+        let t = x as f64 / (bar_width - 1) as f64;
+        let color = heat_color(t).gamma_to_linear().to_rgb32f();
+        for y in 0..bar_height {
+            image.put_pixel(x0 + x, y0 + y, color);
+        }
+    }
+
+    let label_y = y0 + bar_height + 2 * scale;
+    let max_label = max_count.to_string();
+    let max_label_width = max_label.len() as u32 * 4 * scale - scale;
+    draw_text(image, "0", x0, label_y, scale);
+    draw_text(
+        image,
+        &max_label,
+        x0 + bar_width - max_label_width,
+        label_y,
+        scale,
+    );
+}
+
+fn draw_text(image: &mut Rgb32FImage, text: &str, x: u32, y: u32, scale: u32) {
+    let foreground = Color::ones().to_rgb32f();
+
+    for (i, c) in text.chars().enumerate() {
+        let Some(digit) = c.to_digit(10) else {
+            continue;
+        };
+        let glyph_x = x + i as u32 * 4 * scale;
+
+        for (row, bits) in DIGITS[digit as usize].iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let px = glyph_x + col * scale + dx;
+                        let py = y + row as u32 * scale + dy;
+                        if px < image.width() && py < image.height() {
+                            image.put_pixel(px, py, foreground);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
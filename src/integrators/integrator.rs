@@ -0,0 +1,97 @@
+use image::Rgb32FImage;
+use std::fmt::Debug;
+
+use crate::geometry::World;
+use crate::integrators::{
+    AmbientOcclusion, Bdpt, DebugIntegrator, DebugMode, DirectLighting, Heatmap, HeatmapMetric,
+    NaivePathTracer, PathTracer, PhotonMapper, Pssmlt, SpectralPathTracer,
+};
+use crate::optics::{Projection, Ray};
+use crate::utils::{Color, Sampler};
+
+pub trait Integrator: Debug {
+    // Called once per render, before any rays are traced
+    fn prepare(&mut self, _world: &World, _projection: &Projection) {}
+
+    // Called before every pass over the image, each pass takes one sample per pixel
+    fn start_pass(&mut self, _world: &World, _pass: u32, _rng: &mut Sampler) {}
+
+    // Replaces the passes for integrators that choose the image samples themselves
+    fn render(
+        &mut self,
+        _world: &World,
+        _samples_per_pixel: u32,
+        _rng: &mut Sampler,
+    ) -> Option<Rgb32FImage> {
+        None
+    }
+
+    fn ray_color(&self, r: &Ray, world: &World, rng: &mut Sampler) -> Color;
+
+    // Called once per render with the linear image, before it is quantized
+    fn finish(&self, _film: &mut Rgb32FImage) {}
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum IntegratorType {
+    Naive,
+    #[default]
+    PathTracer,
+    AmbientOcclusion,
+    DirectLighting,
+    Bidirectional,
+    PhotonMapping,
+    Metropolis,
+    Spectral,
+    Debug(DebugMode),
+    Heatmap(HeatmapMetric),
+}
+
+impl IntegratorType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "naive" => Some(IntegratorType::Naive),
+            "path" => Some(IntegratorType::PathTracer),
+            "ao" => Some(IntegratorType::AmbientOcclusion),
+            "direct" => Some(IntegratorType::DirectLighting),
+            "bdpt" => Some(IntegratorType::Bidirectional),
+            "photons" => Some(IntegratorType::PhotonMapping),
+            "mlt" => Some(IntegratorType::Metropolis),
+            "spectral" => Some(IntegratorType::Spectral),
+            "normals" => Some(IntegratorType::Debug(DebugMode::Normals)),
+            "tangents" => Some(IntegratorType::Debug(DebugMode::Tangents)),
+            "uv" => Some(IntegratorType::Debug(DebugMode::UV)),
+            "depth" => Some(IntegratorType::Debug(DebugMode::Depth)),
+            "barycentrics" => Some(IntegratorType::Debug(DebugMode::Barycentrics)),
+            "heatmap" => Some(IntegratorType::Heatmap(HeatmapMetric::Total)),
+            "heatmap-traversal" => Some(IntegratorType::Heatmap(HeatmapMetric::TraversalSteps)),
+            "heatmap-primitives" => Some(IntegratorType::Heatmap(HeatmapMetric::PrimitiveTests)),
+            _ => None,
+        }
+    }
+
+    pub fn build(&self, max_depth: u32) -> Box<dyn Integrator> {
+        match self {
+            IntegratorType::Naive => Box::new(NaivePathTracer::new(max_depth)),
+            IntegratorType::PathTracer => Box::new(PathTracer::new(max_depth)),
+            IntegratorType::AmbientOcclusion => Box::new(AmbientOcclusion::new(f64::INFINITY)),
+            IntegratorType::DirectLighting => Box::new(DirectLighting::new(max_depth)),
+            IntegratorType::Bidirectional => Box::new(Bdpt::new(max_depth)),
+            IntegratorType::PhotonMapping => Box::new(PhotonMapper::new(max_depth, 100_000, None)),
+            IntegratorType::Metropolis => Box::new(Pssmlt::new(max_depth, 100_000, 1000)),
+            IntegratorType::Spectral => Box::new(SpectralPathTracer::new(max_depth)),
+            IntegratorType::Debug(mode) => Box::new(DebugIntegrator::new(*mode)),
+            IntegratorType::Heatmap(metric) => {
+                Box::new(Heatmap::new(*metric, metric.default_max()))
+            }
+        }
+    }
+}
+
+pub fn power_heuristic(f_pdf: f64, g_pdf: f64) -> f64 {
+    let (f2, g2) = (f_pdf * f_pdf, g_pdf * g_pdf);
+    if f2 + g2 <= 0.0 {
+        return 0.0;
+    }
+    f2 / (f2 + g2)
+}
@@ -0,0 +1,26 @@
+pub mod ambient_occlusion;
+pub mod bdpt;
+pub mod debug;
+pub mod direct_lighting;
+pub mod heatmap;
+pub mod integrator;
+pub mod naive;
+pub mod path;
+pub mod photon_mapping;
+pub mod pssmlt;
+pub mod scene_lights;
+pub mod spectral;
+
+// Export
+pub use ambient_occlusion::*;
+pub use bdpt::*;
+pub use debug::*;
+pub use direct_lighting::*;
+pub use heatmap::*;
+pub use integrator::*;
+pub use naive::*;
+pub use path::*;
+pub use photon_mapping::*;
+pub use pssmlt::*;
+pub use scene_lights::*;
+pub use spectral::*;
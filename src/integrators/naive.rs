@@ -0,0 +1,52 @@
+use crate::geometry::{HitRecord, Hittable, World};
+use crate::integrators::Integrator;
+use crate::optics::Ray;
+use crate::utils::{Color, Interval, Point3, Sampler, Vec3};
+
+// Brute force path tracing, lights only contribute when hit by chance
+#[derive(Debug)]
+pub struct NaivePathTracer {
+    max_depth: u32,
+}
+
+impl NaivePathTracer {
+    pub fn new(max_depth: u32) -> Self {
+        Self { max_depth }
+    }
+}
+
+impl NaivePathTracer {
+    fn trace(&self, r: &Ray, depth: u32, world: &World, rng: &mut Sampler) -> Color {
+        if depth == 0 {
+            return Color::zeros();
+        }
+
+        let mut rec = HitRecord::default();
+        if world.hit(r, Interval::right_open(0.001), &mut rec) {
+            // Absorbed by the medium along the way
+            let absorbed = r.transmittance(rec.t);
+            let mut scattered = Ray::new(Point3::zeros(), Vec3::zeros(), 0.0);
+            let mut attenuation = Color::zeros();
+
+            let emitted = rec.mat.emitted(r, &rec);
+
+            if rec
+                .mat
+                .scatter(r, &rec, rng, &mut attenuation, &mut scattered)
+            {
+                let incoming = self.trace(&scattered, depth - 1, world, rng);
+                return absorbed.component_mul(&(emitted + attenuation.component_mul(&incoming)));
+            }
+
+            return absorbed.component_mul(&emitted);
+        }
+
+        world.background.sample(r)
+    }
+}
+
+impl Integrator for NaivePathTracer {
+    fn ray_color(&self, r: &Ray, world: &World, rng: &mut Sampler) -> Color {
+        self.trace(r, self.max_depth, world, rng)
+    }
+}
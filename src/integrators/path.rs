@@ -0,0 +1,86 @@
+use crate::geometry::{HitRecord, Hittable, World};
+use crate::integrators::{power_heuristic, Integrator, SceneLights};
+use crate::optics::{Projection, Ray};
+use crate::utils::{Color, Interval, Point3, Sampler, Vec3, Vec3Ext};
+
+// Path tracing with next event estimation, combined with material sampling through MIS
+#[derive(Debug)]
+pub struct PathTracer {
+    max_depth: u32,
+    lights: SceneLights,
+}
+
+impl PathTracer {
+    pub fn new(max_depth: u32) -> Self {
+        Self {
+            max_depth,
+            lights: SceneLights::default(),
+        }
+    }
+}
+
+impl Integrator for PathTracer {
+    fn prepare(&mut self, world: &World, _: &Projection) {
+        self.lights = SceneLights::new(world);
+    }
+
+    fn ray_color(&self, r: &Ray, world: &World, rng: &mut Sampler) -> Color {
+        let mut color = Color::zeros();
+        let mut throughput = Color::ones();
+        let mut ray = r.clone();
+
+        let mut specular_bounce = true;
+        let mut prev_p = Point3::zeros();
+        let mut prev_normal = Vec3::zeros();
+        let mut prev_pdf = 0.0;
+
+        for _ in 0..self.max_depth {
+            let mut rec = HitRecord::default();
+            if !world.hit(&ray, Interval::right_open(0.001), &mut rec) {
+                color += throughput.component_mul(&world.background.sample(&ray));
+                break;
+            }
+
+            // Absorbed by the medium along the way
+            throughput = throughput.component_mul(&ray.transmittance(rec.t));
+
+            let emitted = rec.mat.emitted(&ray, &rec);
+            if !emitted.near_zero() {
+                let weight = match &rec.light {
+                    Some(light) if !specular_bounce => {
+                        let light_pdf = self.lights.pmf(&prev_p, &prev_normal, light)
+                            * light.pdf(&prev_p, &rec.p);
+                        power_heuristic(prev_pdf, light_pdf)
+                    }
+                    _ => 1.0,
+                };
+                color += throughput.component_mul(&emitted) * weight;
+            }
+
+            if !rec.mat.is_specular() {
+                let direct = self.lights.sample_direct(&ray, &rec, world, rng, true);
+                color += throughput.component_mul(&direct);
+            }
+
+            let mut scattered = Ray::new(Point3::zeros(), Vec3::zeros(), 0.0);
+            let mut attenuation = Color::zeros();
+            if !rec
+                .mat
+                .scatter(&ray, &rec, rng, &mut attenuation, &mut scattered)
+            {
+                break;
+            }
+
+            // Smooth spots of rough materials scatter specularly too, with no density to weigh
+            prev_pdf = rec.mat.pdf(&ray, &rec, &scattered.direction());
+            specular_bounce = rec.mat.is_specular() || prev_pdf <= 0.0;
+            prev_p = rec.p;
+            prev_normal = rec.normal;
+
+            throughput = throughput.component_mul(&attenuation);
+            ray = scattered;
+        }
+
+        color
+    }
+}
@@ -0,0 +1,276 @@
+use rand::prelude::Rng;
+use std::f64::consts::PI;
+
+use crate::geometry::accel::{KdTree, AABB};
+use crate::geometry::{HitRecord, Hittable, World};
+use crate::integrators::{power_heuristic, Integrator, SceneLights};
+use crate::lights::LightSampling;
+use crate::optics::{Projection, Ray};
+use crate::utils::{Color, Interval, Point3, Sampler, Vec3, Vec3Ext};
+
+// Radius reduction of progressive photon mapping, between 0 and 1
+const ALPHA: f64 = 2.0 / 3.0;
+
+// Typical number of photons within the derived initial radius
+const NEIGHBOURS: usize = 32;
+
+#[derive(Debug, Clone)]
+struct Photon {
+    direction: Vec3,
+    power: Color,
+}
+
+// Path tracing with caustics taken from a photon map, which is retraced with a smaller radius every
+// pass (Knaus and Zwicker's probabilistic progressive photon mapping)
+#[derive(Debug)]
+pub struct PhotonMapper {
+    max_depth: u32,
+    photons_per_pass: usize,
+    initial_radius: Option<f64>, // Derived from the first caustics if not given
+    radius: f64,
+    lights: SceneLights,
+    emitters: SceneLights,
+    caustics: KdTree<Photon>,
+    emitted: usize,
+}
+
+impl PhotonMapper {
+    pub fn new(max_depth: u32, photons_per_pass: usize, initial_radius: Option<f64>) -> Self {
+        Self {
+            max_depth,
+            photons_per_pass,
+            initial_radius,
+            radius: 0.0,
+            lights: SceneLights::default(),
+            emitters: SceneLights::default(),
+            caustics: KdTree::new(vec![]),
+            emitted: 0,
+        }
+    }
+}
+
+impl PhotonMapper {
+    // Stores photons arriving at a diffuse surface after at least one specular bounce
+    fn trace_photons(&self, world: &World, rng: &mut Sampler) -> Vec<(Point3, Photon)> {
+        let mut photons = vec![];
+
+        for _ in 0..self.photons_per_pass {
+            let Some((light_idx, pmf)) =
+                self.emitters
+                    .sample(&Point3::zeros(), &Vec3::zeros(), rng.gen())
+            else {
+                break;
+            };
+            let Some(emission) = world.lights[light_idx].sample_emission(rng) else {
+                continue;
+            };
+            if emission.pdf_position <= 0.0 || emission.pdf_direction <= 0.0 {
+                continue;
+            }
+
+            let cos_theta = if emission.normal.near_zero() {
+                1.0
+            } else {
+                emission.normal.dot(&emission.ray.direction()).abs()
+            };
+            let mut power = emission.radiance * cos_theta
+                / (pmf * emission.pdf_position * emission.pdf_direction);
+            let mut ray = emission.ray;
+            let mut specular = false;
+
+            for _ in 0..self.max_depth {
+                if power.near_zero() {
+                    break;
+                }
+
+                let mut rec = HitRecord::default();
+                if !world.hit(&ray, Interval::right_open(0.001), &mut rec) {
+                    break;
+                }
+                power = power.component_mul(&ray.transmittance(rec.t));
+
+                let mut scattered = Ray::new(Point3::zeros(), Vec3::zeros(), 0.0);
+                let mut attenuation = Color::zeros();
+                let scatters = rec
+                    .mat
+                    .scatter(&ray, &rec, rng, &mut attenuation, &mut scattered);
+
+                // Smooth spots of rough materials scatter specularly too, as the camera path sees it
+                let specular_here = rec.mat.is_specular()
+                    || (scatters && rec.mat.pdf(&ray, &rec, &scattered.direction()) <= 0.0);
+                if !specular_here {
+                    // Volumes have no surface to gather caustics on
+                    if specular && !rec.normal.near_zero() {
+                        let direction = ray.direction();
+                        photons.push((rec.p, Photon { direction, power }));
+                    }
+                    break;
+                }
+                if !scatters {
+                    break;
+                }
+
+                power = power.component_mul(&attenuation);
+                specular = true;
+                ray = scattered;
+            }
+        }
+
+        photons
+    }
+
+    // Grows the radius until the median of a few photons has enough neighbours
+    fn estimate_radius(&self) -> f64 {
+        let items = self.caustics.items();
+        if items.len() <= NEIGHBOURS {
+            return 0.0;
+        }
+
+        let bbox = items.iter().fold(AABB::empty(), |bbox, (p, _)| {
+            AABB::wrap_boxes(&bbox, &AABB::wrap_points(p, p))
+        });
+        let diagonal = Vec3::new(bbox.x().size(), bbox.y().size(), bbox.z().size()).norm();
+        let step = (items.len() / NEIGHBOURS).max(1);
+
+        let mut radius = 1e-5 * diagonal;
+        while radius < diagonal {
+            let mut counts: Vec<usize> = items
+                .iter()
+                .step_by(step)
+                .map(|(p, _)| {
+                    let mut count = 0;
+                    self.caustics.for_each_within(p, radius, |_, _| count += 1);
+                    count
+                })
+                .collect();
+            counts.sort_unstable();
+
+            if counts[counts.len() / 2] >= NEIGHBOURS {
+                break;
+            }
+            radius *= 1.5;
+        }
+
+        radius
+    }
+
+    fn caustic_radiance(&self, r: &Ray, rec: &HitRecord) -> Color {
+        if self.caustics.is_empty() || self.radius <= 0.0 {
+            return Color::zeros();
+        }
+
+        let mut sum = Color::zeros();
+        self.caustics
+            .for_each_within(&rec.p, self.radius, |_, photon| {
+                let wi = -photon.direction;
+                let cos_theta = rec.normal.dot(&wi);
+                if cos_theta <= 0.0 {
+                    return;
+                }
+
+                // Photon power already accounts for the projected area
+                let f = rec.mat.eval(r, rec, &wi) / cos_theta;
+                sum += f.component_mul(&photon.power);
+            });
+
+        sum / (self.emitted as f64 * PI * self.radius * self.radius)
+    }
+}
+
+impl Integrator for PhotonMapper {
+    fn prepare(&mut self, world: &World, _: &Projection) {
+        self.lights = SceneLights::new(world);
+        self.emitters = SceneLights::from_sampling(world, LightSampling::Power);
+        self.radius = self.initial_radius.unwrap_or(0.0);
+    }
+
+    fn start_pass(&mut self, world: &World, pass: u32, rng: &mut Sampler) {
+        let photons = self.trace_photons(world, rng);
+
+        self.emitted = self.photons_per_pass;
+        self.caustics = KdTree::new(photons);
+
+        if self.radius <= 0.0 {
+            self.radius = self.estimate_radius();
+        } else if pass > 0 {
+            self.radius *= ((pass as f64 - 1.0 + ALPHA) / pass as f64).sqrt();
+        }
+    }
+
+    fn ray_color(&self, r: &Ray, world: &World, rng: &mut Sampler) -> Color {
+        let mut color = Color::zeros();
+        let mut throughput = Color::ones();
+        let mut ray = r.clone();
+
+        let mut specular_bounce = true;
+        let mut after_diffuse = false;
+        let mut caustic_path = false; // Specular bounces only, since the last diffuse one
+        let mut prev_p = Point3::zeros();
+        let mut prev_normal = Vec3::zeros();
+        let mut prev_pdf = 0.0;
+
+        for _ in 0..self.max_depth {
+            let mut rec = HitRecord::default();
+            if !world.hit(&ray, Interval::right_open(0.001), &mut rec) {
+                color += throughput.component_mul(&world.background.sample(&ray));
+                break;
+            }
+
+            // Absorbed by the medium along the way
+            throughput = throughput.component_mul(&ray.transmittance(rec.t));
+
+            let emitted = rec.mat.emitted(&ray, &rec);
+            let from_photons = caustic_path
+                && rec
+                    .light
+                    .as_ref()
+                    .is_some_and(|light| self.emitters.contains(light));
+
+            if !emitted.near_zero() && !from_photons {
+                let weight = match &rec.light {
+                    Some(light) if !specular_bounce => {
+                        let light_pdf = self.lights.pmf(&prev_p, &prev_normal, light)
+                            * light.pdf(&prev_p, &rec.p);
+                        power_heuristic(prev_pdf, light_pdf)
+                    }
+                    _ => 1.0,
+                };
+                color += throughput.component_mul(&emitted) * weight;
+            }
+
+            if !rec.mat.is_specular() {
+                let direct = self.lights.sample_direct(&ray, &rec, world, rng, true);
+                let caustics = self.caustic_radiance(&ray, &rec);
+                color += throughput.component_mul(&(direct + caustics));
+            }
+
+            let mut scattered = Ray::new(Point3::zeros(), Vec3::zeros(), 0.0);
+            let mut attenuation = Color::zeros();
+            if !rec
+                .mat
+                .scatter(&ray, &rec, rng, &mut attenuation, &mut scattered)
+            {
+                break;
+            }
+
+            // Smooth spots of rough materials scatter specularly too, with no density to weigh
+            prev_pdf = rec.mat.pdf(&ray, &rec, &scattered.direction());
+            specular_bounce = rec.mat.is_specular() || prev_pdf <= 0.0;
+            if specular_bounce {
+                caustic_path = after_diffuse;
+            } else {
+                // Caustics behind volume scattering are left to the path tracer
+                after_diffuse = !rec.normal.near_zero();
+                caustic_path = false;
+            }
+
+            prev_p = rec.p;
+            prev_normal = rec.normal;
+
+            throughput = throughput.component_mul(&attenuation);
+            ray = scattered;
+        }
+
+        color
+    }
+}
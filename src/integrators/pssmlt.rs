@@ -0,0 +1,142 @@
+use image::{ImageBuffer, Rgb32FImage};
+use rand::prelude::Rng;
+use tqdm::tqdm;
+
+use crate::geometry::World;
+use crate::integrators::{Integrator, PathTracer};
+use crate::optics::{Projection, Ray};
+use crate::utils::{AliasTable, Color, ColorExt, PrimarySampleSpace, Sampler};
+
+// Standard deviation of small steps in primary sample space
+const SIGMA: f64 = 0.01;
+
+const LARGE_STEP_PROBABILITY: f64 = 0.3;
+
+// Primary sample space Metropolis light transport (Kelemen et al.), mutating the random numbers
+// consumed by the path tracer, including the raster position, instead of sampling every pixel
+#[derive(Debug)]
+pub struct Pssmlt {
+    path: PathTracer,
+    projection: Projection,
+    bootstrap_samples: usize,
+    chains: usize,
+}
+
+impl Pssmlt {
+    pub fn new(max_depth: u32, bootstrap_samples: usize, chains: usize) -> Self {
+        Self {
+            path: PathTracer::new(max_depth),
+            projection: Projection::default(),
+            bootstrap_samples,
+            chains,
+        }
+    }
+}
+
+impl Pssmlt {
+    fn sampler(seed: u64) -> Sampler {
+        Sampler::PrimarySample(Box::new(PrimarySampleSpace::new(
+            seed,
+            SIGMA,
+            LARGE_STEP_PROBABILITY,
+        )))
+    }
+
+    fn primary_sample_space(sampler: &mut Sampler) -> &mut PrimarySampleSpace {
+        let Sampler::PrimarySample(space) = sampler else {
+            unreachable!("Metropolis chains mutate primary samples")
+        };
+        space
+    }
+
+    // Path contribution of the current primary samples and the pixel it lands in
+    fn contribution(&self, world: &World, sampler: &mut Sampler) -> (Color, usize) {
+        let width = self.projection.image_width();
+        let height = self.projection.image_height();
+
+        let x = sampler.gen::<f64>() * width as f64;
+        let y = sampler.gen::<f64>() * height as f64;
+        let r = self.projection.ray(x, y, sampler);
+
+        let pixel = (y as u32).min(height - 1) * width + (x as u32).min(width - 1);
+        (self.path.ray_color(&r, world, sampler), pixel as usize)
+    }
+}
+
+impl Integrator for Pssmlt {
+    fn prepare(&mut self, world: &World, projection: &Projection) {
+        self.path.prepare(world, projection);
+        self.projection = projection.clone();
+    }
+
+    fn render(
+        &mut self,
+        world: &World,
+        samples_per_pixel: u32,
+        rng: &mut Sampler,
+    ) -> Option<Rgb32FImage> {
+        let width = self.projection.image_width();
+        let height = self.projection.image_height();
+        let mutations = samples_per_pixel as usize * (width * height) as usize;
+        let seed: u64 = rng.gen();
+
+        // Bootstrap, estimates the image brightness and seeds the chains proportionally to it
+        let weights: Vec<f64> = (0..self.bootstrap_samples)
+            .map(|i| {
+                let mut sampler = Self::sampler(seed.wrapping_add(i as u64));
+                self.contribution(world, &mut sampler).0.luminance()
+            })
+            .collect();
+        let brightness = weights.iter().sum::<f64>() / self.bootstrap_samples.max(1) as f64;
+
+        let mut splats = vec![Color::zeros(); (width * height) as usize];
+        let seeds = AliasTable::new(&weights);
+        let chains = self.chains.min(mutations);
+
+        for chain in tqdm(0..chains) {
+            let Some((idx, _)) = seeds.sample(rng.gen()) else {
+                break;
+            };
+            let mut sampler = Self::sampler(seed.wrapping_add(idx as u64));
+            let mut current = self.contribution(world, &mut sampler);
+
+            let chain_mutations = mutations / chains + usize::from(chain < mutations % chains);
+            for _ in 0..chain_mutations {
+                Self::primary_sample_space(&mut sampler).start_iteration();
+                let proposed = self.contribution(world, &mut sampler);
+
+                let current_luminance = current.0.luminance();
+                let proposed_luminance = proposed.0.luminance();
+                let accept = if current_luminance > 0.0 {
+                    (proposed_luminance / current_luminance).min(1.0)
+                } else {
+                    1.0
+                };
+
+                // Expected values, both states contribute according to the acceptance
+                if accept > 0.0 && proposed_luminance > 0.0 {
+                    splats[proposed.1] += proposed.0 * accept / proposed_luminance;
+                }
+                if accept < 1.0 {
+                    splats[current.1] += current.0 * (1.0 - accept) / current_luminance;
+                }
+
+                if rng.gen::<f64>() < accept {
+                    current = proposed;
+                    Self::primary_sample_space(&mut sampler).accept();
+                } else {
+                    Self::primary_sample_space(&mut sampler).reject();
+                }
+            }
+        }
+
+        Some(ImageBuffer::from_fn(width, height, |i, j| {
+            let splat = splats[(j * width + i) as usize];
+            (splat * brightness / samples_per_pixel as f64).to_rgb32f()
+        }))
+    }
+
+    fn ray_color(&self, r: &Ray, world: &World, rng: &mut Sampler) -> Color {
+        self.path.ray_color(r, world, rng)
+    }
+}
@@ -0,0 +1,136 @@
+use rand::prelude::Rng;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::geometry::{HitRecord, World};
+use crate::integrators::power_heuristic;
+use crate::lights::{Light, LightSampler, LightSampling, UniformLightSampler};
+use crate::optics::Ray;
+use crate::utils::{Color, Point3, Sampler, Vec3, Vec3Ext};
+
+// Terms of a light sample kept apart, so that spectral rendering can upsample each of them
+#[derive(Debug, Clone)]
+pub struct DirectSample {
+    pub f: Color,
+    pub radiance: Color,
+    pub transmittance: Color,
+    pub weight: f64, // MIS weight over the light density
+}
+
+impl DirectSample {
+    pub fn contribution(&self) -> Color {
+        self.f
+            .component_mul(&self.radiance)
+            .component_mul(&self.transmittance)
+            * self.weight
+    }
+}
+
+// Light selection for a world, shared by the integrators that sample lights directly
+#[derive(Debug)]
+pub struct SceneLights {
+    sampler: Box<dyn LightSampler>,
+    indices: HashMap<*const (), usize>,
+}
+
+impl SceneLights {
+    pub fn new(world: &World) -> Self {
+        Self::from_sampling(world, world.light_sampling)
+    }
+
+    pub fn from_sampling(world: &World, light_sampling: LightSampling) -> Self {
+        let indices = world
+            .lights
+            .iter()
+            .enumerate()
+            .map(|(i, light)| (Rc::as_ptr(light) as *const (), i))
+            .collect();
+
+        Self {
+            sampler: light_sampling.build(&world.lights),
+            indices,
+        }
+    }
+}
+
+impl Default for SceneLights {
+    fn default() -> Self {
+        Self {
+            sampler: Box::new(UniformLightSampler::new(&[])),
+            indices: HashMap::new(),
+        }
+    }
+}
+
+impl SceneLights {
+    pub fn sample(&self, p: &Point3, n: &Vec3, u: f64) -> Option<(usize, f64)> {
+        self.sampler.sample(p, n, u)
+    }
+
+    pub fn contains(&self, light: &Rc<dyn Light>) -> bool {
+        self.indices.contains_key(&(Rc::as_ptr(light) as *const ()))
+    }
+
+    // Probability of picking `light` for a receiver at `p` with normal `n`
+    pub fn pmf(&self, p: &Point3, n: &Vec3, light: &Rc<dyn Light>) -> f64 {
+        match self.indices.get(&(Rc::as_ptr(light) as *const ())) {
+            Some(&idx) => self.sampler.pmf(p, n, idx),
+            None => 0.0,
+        }
+    }
+
+    // One light sample, optionally MIS weighted against sampling the material
+    pub fn sample_direct(
+        &self,
+        r: &Ray,
+        rec: &HitRecord,
+        world: &World,
+        rng: &mut Sampler,
+        mis: bool,
+    ) -> Color {
+        self.sample_direct_terms(r, rec, world, rng, mis)
+            .map_or(Color::zeros(), |sample| sample.contribution())
+    }
+
+    pub fn sample_direct_terms(
+        &self,
+        r: &Ray,
+        rec: &HitRecord,
+        world: &World,
+        rng: &mut Sampler,
+        mis: bool,
+    ) -> Option<DirectSample> {
+        let (light_idx, pmf) = self.sampler.sample(&rec.p, &rec.normal, rng.gen())?;
+        let light = &world.lights[light_idx];
+        let sample = light.sample(&rec.p, rng)?;
+
+        let f = rec.mat.eval(r, rec, &sample.direction);
+        if f.near_zero() {
+            return None;
+        }
+
+        let media = rec
+            .mat
+            .media_towards(r, rec, &sample.direction)
+            .unwrap_or_else(|| r.media().to_vec());
+        let shadow_ray = Ray::new(rec.p, sample.direction, r.time()).with_media(media);
+        let transmittance = world.transmittance_towards(&shadow_ray, sample.distance);
+        if transmittance.near_zero() {
+            return None;
+        }
+
+        let light_pdf = sample.pdf * pmf;
+        let weight = if mis && !light.is_delta() {
+            power_heuristic(light_pdf, rec.mat.pdf(r, rec, &sample.direction))
+        } else {
+            1.0
+        };
+
+        Some(DirectSample {
+            f,
+            radiance: sample.radiance,
+            transmittance,
+            weight: weight / light_pdf,
+        })
+    }
+}
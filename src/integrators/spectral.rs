@@ -0,0 +1,113 @@
+use rand::prelude::Rng;
+
+use crate::geometry::{HitRecord, Hittable, World};
+use crate::integrators::{power_heuristic, Integrator, SceneLights};
+use crate::optics::{Projection, Ray};
+use crate::utils::{
+    Color, Interval, Point3, SampledSpectrum, SampledWavelengths, Sampler, SpectralFilm, Vec3,
+    Vec3Ext,
+};
+
+// Path tracing at a few wavelengths at once (hero wavelength sampling), with the RGB colors of
+// materials and lights upsampled to spectra at every vertex. Dispersive dielectrics leave only the
+// hero wavelength on the path.
+#[derive(Debug)]
+pub struct SpectralPathTracer {
+    max_depth: u32,
+    lights: SceneLights,
+    film: SpectralFilm,
+}
+
+impl SpectralPathTracer {
+    pub fn new(max_depth: u32) -> Self {
+        Self {
+            max_depth,
+            lights: SceneLights::default(),
+            film: SpectralFilm::new(),
+        }
+    }
+}
+
+impl Integrator for SpectralPathTracer {
+    fn prepare(&mut self, world: &World, _: &Projection) {
+        self.lights = SceneLights::new(world);
+    }
+
+    fn ray_color(&self, r: &Ray, world: &World, rng: &mut Sampler) -> Color {
+        let mut wavelengths = SampledWavelengths::sample_visible(rng.gen());
+        let mut radiance = SampledSpectrum::zeros();
+        let mut throughput = SampledSpectrum::from_element(1.0);
+        let mut ray = r.clone().with_wavelengths(wavelengths);
+
+        let mut specular_bounce = true;
+        let mut prev_p = Point3::zeros();
+        let mut prev_normal = Vec3::zeros();
+        let mut prev_pdf = 0.0;
+
+        for _ in 0..self.max_depth {
+            let mut rec = HitRecord::default();
+            if !world.hit(&ray, Interval::right_open(0.001), &mut rec) {
+                let background = wavelengths.upsample(&world.background.sample(&ray));
+                radiance += throughput.component_mul(&background);
+                break;
+            }
+
+            // Absorbed by the medium along the way
+            throughput = throughput.component_mul(&wavelengths.upsample(&ray.transmittance(rec.t)));
+
+            let emitted = rec.mat.emitted(&ray, &rec);
+            if !emitted.near_zero() {
+                let weight = match &rec.light {
+                    Some(light) if !specular_bounce => {
+                        let light_pdf = self.lights.pmf(&prev_p, &prev_normal, light)
+                            * light.pdf(&prev_p, &rec.p);
+                        power_heuristic(prev_pdf, light_pdf)
+                    }
+                    _ => 1.0,
+                };
+                radiance += throughput.component_mul(&wavelengths.upsample(&emitted)) * weight;
+            }
+
+            // Upsampled term by term like along material sampled paths, so that MIS weighs
+            // estimates of the same spectrum
+            if !rec.mat.is_specular() {
+                if let Some(direct) = self
+                    .lights
+                    .sample_direct_terms(&ray, &rec, world, rng, true)
+                {
+                    let direct = wavelengths
+                        .upsample(&direct.f)
+                        .component_mul(&wavelengths.upsample(&direct.radiance))
+                        .component_mul(&wavelengths.upsample(&direct.transmittance))
+                        * direct.weight;
+                    radiance += throughput.component_mul(&direct);
+                }
+            }
+
+            let mut scattered = Ray::new(Point3::zeros(), Vec3::zeros(), 0.0);
+            let mut attenuation = Color::zeros();
+            if !rec
+                .mat
+                .scatter(&ray, &rec, rng, &mut attenuation, &mut scattered)
+            {
+                break;
+            }
+
+            // Smooth spots of rough materials scatter specularly too, with no density to weigh
+            prev_pdf = rec.mat.pdf(&ray, &rec, &scattered.direction());
+            specular_bounce = rec.mat.is_specular() || prev_pdf <= 0.0;
+            prev_p = rec.p;
+            prev_normal = rec.normal;
+
+            // The material may have terminated the secondary wavelengths
+            if let Some(next) = scattered.wavelengths() {
+                wavelengths = *next;
+            }
+
+            throughput = throughput.component_mul(&wavelengths.upsample(&attenuation));
+            ray = scattered;
+        }
+
+        self.film.to_rgb(&wavelengths, &radiance)
+    }
+}
@@ -1,5 +1,7 @@
 pub mod examples;
 pub mod geometry;
+pub mod integrators;
+pub mod lights;
 pub mod materials;
 pub mod optics;
 pub mod textures;
@@ -0,0 +1,161 @@
+use std::path::Path;
+
+use crate::utils::{Onb, Vec3, Vec3Ext};
+
+// IESNA LM-63 photometric data (type C photometry)
+#[derive(Debug, Clone)]
+pub struct IesProfile {
+    vertical_angles: Vec<f64>,
+    horizontal_angles: Vec<f64>,
+    candela: Vec<Vec<f64>>, // Indexed by [horizontal][vertical]
+    max_candela: f64,
+}
+
+impl IesProfile {
+    pub fn new(ies_path: &str) -> Self {
+        let ies_file = std::fs::read_to_string(Path::new(ies_path)).unwrap();
+        Self::parse(&ies_file)
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let tilt_pos = contents
+            .find("TILT=")
+            .expect("IES file is missing the TILT line");
+        let mut lines = contents[tilt_pos..].lines();
+        let tilt = lines.next().unwrap()["TILT=".len()..].trim();
+
+        let rest: Vec<&str> = lines.collect();
+        let mut values = rest
+            .iter()
+            .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ','))
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<f64>().expect("Invalid number in IES file"));
+        let mut next = || values.next().expect("Unexpected end of IES file");
+
+        // Lamp tilt data does not affect the luminaire distribution and is skipped
+        if tilt == "INCLUDE" {
+            let _lamp_to_luminaire_geometry = next();
+            let num_pairs = next() as usize;
+            for _ in 0..2 * num_pairs {
+                next();
+            }
+        }
+
+        let _num_lamps = next();
+        let _lumens_per_lamp = next();
+        let multiplier = next();
+        let num_vertical = next() as usize;
+        let num_horizontal = next() as usize;
+        let photometric_type = next() as u32;
+        let _units = next();
+        let (_width, _length, _height) = (next(), next(), next());
+        let ballast_factor = next();
+        let _ballast_lamp_factor = next();
+        let _input_watts = next();
+
+        if photometric_type != 1 {
+            panic!("Only type C photometry is supported");
+        }
+
+        let vertical_angles: Vec<f64> = (0..num_vertical).map(|_| next()).collect();
+        let horizontal_angles: Vec<f64> = (0..num_horizontal).map(|_| next()).collect();
+        let candela: Vec<Vec<f64>> = (0..num_horizontal)
+            .map(|_| {
+                (0..num_vertical)
+                    .map(|_| next() * multiplier * ballast_factor)
+                    .collect()
+            })
+            .collect();
+
+        let max_candela = candela.iter().flatten().fold(0.0_f64, |acc, &c| acc.max(c));
+
+        Self {
+            vertical_angles,
+            horizontal_angles,
+            candela,
+            max_candela,
+        }
+    }
+}
+
+impl IesProfile {
+    pub fn max_candela(&self) -> f64 {
+        self.max_candela
+    }
+
+    // Angles in degrees, vertical angle 0 points along the photometric axis (nadir)
+    pub fn candela(&self, vertical: f64, horizontal: f64) -> f64 {
+        let horizontal = self.fold_horizontal(horizontal);
+
+        let Some((v0, v1, tv)) = Self::bracket(&self.vertical_angles, vertical) else {
+            return 0.0;
+        };
+        let Some((h0, h1, th)) = Self::bracket(&self.horizontal_angles, horizontal) else {
+            return 0.0;
+        };
+
+        let c0 = self.candela[h0][v0] * (1.0 - tv) + self.candela[h0][v1] * tv;
+        let c1 = self.candela[h1][v0] * (1.0 - tv) + self.candela[h1][v1] * tv;
+        c0 * (1.0 - th) + c1 * th
+    }
+
+    pub fn normalized(&self, vertical: f64, horizontal: f64) -> f64 {
+        if self.max_candela <= 0.0 {
+            return 0.0;
+        }
+        self.candela(vertical, horizontal) / self.max_candela
+    }
+
+    // Normalized intensity towards a world direction, `frame.w()` being the photometric axis
+    pub fn direction_factor(&self, frame: &Onb, direction: &Vec3) -> f64 {
+        let local = frame.to_local(&direction.normalize());
+        let vertical = local.z().clamp(-1.0, 1.0).acos().to_degrees();
+        let horizontal = local.y().atan2(local.x()).to_degrees();
+        self.normalized(vertical, horizontal)
+    }
+
+    fn fold_horizontal(&self, horizontal: f64) -> f64 {
+        let h = horizontal.rem_euclid(360.0);
+        let last = *self.horizontal_angles.last().unwrap();
+
+        if last <= 0.0 {
+            // Rotationally symmetric
+            0.0
+        } else if last <= 90.0 {
+            // Quadrant symmetric
+            let h = if h > 180.0 { 360.0 - h } else { h };
+            if h > 90.0 {
+                180.0 - h
+            } else {
+                h
+            }
+        } else if last <= 180.0 {
+            // Bilaterally symmetric
+            if h > 180.0 {
+                360.0 - h
+            } else {
+                h
+            }
+        } else {
+            h
+        }
+    }
+
+    fn bracket(angles: &[f64], x: f64) -> Option<(usize, usize, f64)> {
+        let first = *angles.first()?;
+        let last = *angles.last()?;
+        if angles.len() == 1 {
+            return Some((0, 0, 0.0));
+        }
+        if x < first || x > last {
+            return None;
+        }
+
+        let i = angles
+            .partition_point(|&a| a <= x)
+            .clamp(1, angles.len() - 1);
+        let (a0, a1) = (angles[i - 1], angles[i]);
+        let t = if a1 > a0 { (x - a0) / (a1 - a0) } else { 0.0 };
+        Some((i - 1, i, t))
+    }
+}
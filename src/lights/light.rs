@@ -0,0 +1,44 @@
+use std::fmt::Debug;
+
+use crate::lights::LightBounds;
+use crate::optics::Ray;
+use crate::utils::{Color, Point3, Sampler, Vec3};
+
+#[derive(Debug, Clone)]
+pub struct LightSample {
+    pub direction: Vec3,
+    pub distance: f64,
+    pub radiance: Color,
+    pub pdf: f64,
+    pub normal: Vec3, // Zero for lights without a surface
+}
+
+// Ray leaving the light, for tracing paths starting at the light
+#[derive(Debug, Clone)]
+pub struct LightEmission {
+    pub ray: Ray,
+    pub normal: Vec3, // Zero for lights without a surface
+    pub radiance: Color,
+    pub pdf_position: f64,
+    pub pdf_direction: f64,
+}
+
+pub trait Light: Debug {
+    fn sample(&self, p: &Point3, rng: &mut Sampler) -> Option<LightSample>;
+
+    fn sample_emission(&self, rng: &mut Sampler) -> Option<LightEmission>;
+
+    // Area and solid angle densities of `sample_emission` leaving `light_point` along `direction`
+    fn pdf_emission(&self, light_point: &Point3, direction: &Vec3) -> (f64, f64);
+
+    fn bounds(&self) -> LightBounds;
+
+    fn is_delta(&self) -> bool {
+        false
+    }
+
+    // Solid angle density of sampling `light_point` from `p`, zero for delta lights
+    fn pdf(&self, _p: &Point3, _light_point: &Point3) -> f64 {
+        0.0
+    }
+}
@@ -0,0 +1,163 @@
+extern crate nalgebra as na;
+
+use std::f64::consts::PI;
+
+use crate::geometry::accel::AABB;
+use crate::utils::{Point3, Vec3};
+
+// Spatial and directional bounds of the emission of one or more lights
+#[derive(Debug, Clone)]
+pub struct LightBounds {
+    pub bbox: AABB,
+    pub w: Vec3,
+    pub phi: f64,
+    pub cos_theta_o: f64,
+    pub cos_theta_e: f64,
+    pub two_sided: bool,
+}
+
+impl LightBounds {
+    pub fn new(
+        bbox: AABB,
+        w: Vec3,
+        phi: f64,
+        cos_theta_o: f64,
+        cos_theta_e: f64,
+        two_sided: bool,
+    ) -> Self {
+        Self {
+            bbox,
+            w: w.normalize(),
+            phi,
+            cos_theta_o,
+            cos_theta_e,
+            two_sided,
+        }
+    }
+
+    // Omnidirectional emitter located at a single point
+    pub fn point(p: &Point3, phi: f64) -> Self {
+        Self::new(
+            AABB::wrap_points(p, p),
+            Vec3::new(0.0, 0.0, 1.0),
+            phi,
+            -1.0,
+            0.0,
+            false,
+        )
+    }
+
+    pub fn union(a: &LightBounds, b: &LightBounds) -> Self {
+        if a.phi <= 0.0 {
+            return b.clone();
+        }
+        if b.phi <= 0.0 {
+            return a.clone();
+        }
+
+        let (w, cos_theta_o) = Self::cone_union(&a.w, a.cos_theta_o, &b.w, b.cos_theta_o);
+
+        Self {
+            bbox: AABB::wrap_boxes(&a.bbox, &b.bbox),
+            w,
+            phi: a.phi + b.phi,
+            cos_theta_o,
+            cos_theta_e: a.cos_theta_e.min(b.cos_theta_e),
+            two_sided: a.two_sided || b.two_sided,
+        }
+    }
+
+    fn cone_union(w_a: &Vec3, cos_a: f64, w_b: &Vec3, cos_b: f64) -> (Vec3, f64) {
+        let theta_a = cos_a.clamp(-1.0, 1.0).acos();
+        let theta_b = cos_b.clamp(-1.0, 1.0).acos();
+        let theta_d = w_a.dot(w_b).clamp(-1.0, 1.0).acos();
+
+        if (theta_d + theta_b).min(PI) <= theta_a {
+            return (*w_a, cos_a);
+        }
+        if (theta_d + theta_a).min(PI) <= theta_b {
+            return (*w_b, cos_b);
+        }
+
+        let theta_o = (theta_a + theta_d + theta_b) / 2.0;
+        if theta_o >= PI {
+            return (*w_a, -1.0);
+        }
+
+        let axis = w_a.cross(w_b);
+        if axis.norm_squared() == 0.0 {
+            return (*w_a, -1.0);
+        }
+
+        let rotation =
+            na::Rotation3::from_axis_angle(&na::Unit::new_normalize(axis), theta_o - theta_a);
+        (rotation * w_a, theta_o.cos())
+    }
+}
+
+impl LightBounds {
+    pub fn centroid(&self) -> Point3 {
+        self.bbox.center()
+    }
+
+    // Conservative estimate of the contribution to a receiver at `p` with normal `n` (pbrt-v4)
+    pub fn importance(&self, p: &Point3, n: &Vec3) -> f64 {
+        let pc = self.bbox.center();
+        let diagonal = (self.bbox.max - self.bbox.min).norm();
+        let d2 = (p - pc).norm_squared().max(diagonal / 2.0);
+
+        let wi = (p - pc).normalize();
+        let mut cos_theta_w = self.w.dot(&wi);
+        if self.two_sided {
+            cos_theta_w = cos_theta_w.abs();
+        }
+        let sin_theta_w = safe_sqrt(1.0 - cos_theta_w * cos_theta_w);
+
+        // Angle subtended by the bounding sphere of the box
+        let radius = diagonal / 2.0;
+        let cos_theta_b = if (p - pc).norm_squared() < radius * radius {
+            -1.0
+        } else {
+            safe_sqrt(1.0 - radius * radius / (p - pc).norm_squared())
+        };
+        let sin_theta_b = safe_sqrt(1.0 - cos_theta_b * cos_theta_b);
+
+        let sin_theta_o = safe_sqrt(1.0 - self.cos_theta_o * self.cos_theta_o);
+        let cos_theta_x = cos_sub_clamped(sin_theta_w, cos_theta_w, sin_theta_o, self.cos_theta_o);
+        let sin_theta_x = sin_sub_clamped(sin_theta_w, cos_theta_w, sin_theta_o, self.cos_theta_o);
+        let cos_theta_p = cos_sub_clamped(sin_theta_x, cos_theta_x, sin_theta_b, cos_theta_b);
+        if cos_theta_p <= self.cos_theta_e {
+            return 0.0;
+        }
+
+        let mut importance = self.phi * cos_theta_p / d2;
+
+        if n.norm_squared() > 0.0 {
+            let cos_theta_i = wi.dot(n).abs();
+            let sin_theta_i = safe_sqrt(1.0 - cos_theta_i * cos_theta_i);
+            importance *= cos_sub_clamped(sin_theta_i, cos_theta_i, sin_theta_b, cos_theta_b);
+        }
+
+        importance.max(0.0)
+    }
+}
+
+fn safe_sqrt(x: f64) -> f64 {
+    x.max(0.0).sqrt()
+}
+
+// cos(max(0, a - b))
+fn cos_sub_clamped(sin_a: f64, cos_a: f64, sin_b: f64, cos_b: f64) -> f64 {
+    if cos_a > cos_b {
+        return 1.0;
+    }
+    cos_a * cos_b + sin_a * sin_b
+}
+
+// sin(max(0, a - b))
+fn sin_sub_clamped(sin_a: f64, cos_a: f64, sin_b: f64, cos_b: f64) -> f64 {
+    if cos_a > cos_b {
+        return 0.0;
+    }
+    sin_a * cos_b - cos_a * sin_b
+}
@@ -0,0 +1,283 @@
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::geometry::accel::AABB;
+use crate::lights::{Light, LightBounds, LightSampler};
+use crate::utils::{Point3, Vec3};
+
+const NUM_BUCKETS: usize = 12;
+const MAX_DEPTH: u32 = 48;
+// Bit trails hold one bit per level, deeper lights share a leaf
+const MAX_TRAIL_DEPTH: u32 = u64::BITS;
+
+#[derive(Debug, Clone)]
+enum LightBvhNode {
+    Leaf(LightBounds, Vec<usize>),
+    Inner(LightBounds, usize, usize),
+}
+
+impl LightBvhNode {
+    fn bounds(&self) -> &LightBounds {
+        match self {
+            Self::Leaf(bounds, _) => bounds,
+            Self::Inner(bounds, _, _) => bounds,
+        }
+    }
+}
+
+// Light BVH over spatial bounds and orientation cones (Conty & Kulla, pbrt-v4)
+#[derive(Debug, Clone)]
+pub struct BvhLightSampler {
+    nodes: Vec<LightBvhNode>,
+    bit_trails: Vec<Option<u64>>,
+}
+
+impl BvhLightSampler {
+    pub fn new(lights: &[Rc<dyn Light>]) -> Self {
+        let mut bvh_lights: Vec<(usize, LightBounds)> = lights
+            .iter()
+            .enumerate()
+            .map(|(i, light)| (i, light.bounds()))
+            .filter(|(_, bounds)| bounds.phi > 0.0)
+            .collect();
+
+        let mut sampler = Self {
+            nodes: Vec::new(),
+            bit_trails: vec![None; lights.len()],
+        };
+
+        if !bvh_lights.is_empty() {
+            sampler.build(&mut bvh_lights, 0, 0);
+        }
+
+        sampler
+    }
+
+    fn build(
+        &mut self,
+        bvh_lights: &mut [(usize, LightBounds)],
+        bit_trail: u64,
+        depth: u32,
+    ) -> usize {
+        let curr_idx = self.nodes.len();
+
+        let bounds = bvh_lights
+            .iter()
+            .skip(1)
+            .fold(bvh_lights[0].1.clone(), |acc, (_, b)| {
+                LightBounds::union(&acc, b)
+            });
+
+        if bvh_lights.len() == 1 || depth >= MAX_TRAIL_DEPTH {
+            let light_indices: Vec<usize> = bvh_lights.iter().map(|(i, _)| *i).collect();
+            for &light_idx in &light_indices {
+                self.bit_trails[light_idx] = Some(bit_trail);
+            }
+            self.nodes.push(LightBvhNode::Leaf(bounds, light_indices));
+            return curr_idx;
+        }
+
+        self.nodes
+            .push(LightBvhNode::Inner(bounds.clone(), usize::MAX, usize::MAX));
+
+        let mid = if depth < MAX_DEPTH {
+            Self::find_split(bvh_lights, &bounds)
+        } else {
+            None
+        }
+        .unwrap_or_else(|| {
+            // Median split along the longest centroid axis
+            let centroids = Self::centroid_bounds(bvh_lights);
+            let axis = centroids.longest_axis().idx();
+            bvh_lights.sort_by(|a, b| {
+                a.1.centroid()[axis]
+                    .partial_cmp(&b.1.centroid()[axis])
+                    .unwrap()
+            });
+            bvh_lights.len() / 2
+        });
+
+        let (left, right) = bvh_lights.split_at_mut(mid);
+        let left_idx = self.build(left, bit_trail, depth + 1);
+        let right_idx = self.build(right, bit_trail | (1 << depth), depth + 1);
+
+        if let LightBvhNode::Inner(_, l, r) = &mut self.nodes[curr_idx] {
+            *l = left_idx;
+            *r = right_idx;
+        }
+
+        curr_idx
+    }
+
+    fn centroid_bounds(bvh_lights: &[(usize, LightBounds)]) -> AABB {
+        bvh_lights.iter().fold(AABB::empty(), |acc, (_, b)| {
+            AABB::wrap_boxes(&acc, &AABB::wrap_points(&b.centroid(), &b.centroid()))
+        })
+    }
+
+    // Surface area orientation heuristic, partitions the lights and returns the split index
+    fn find_split(bvh_lights: &mut [(usize, LightBounds)], bounds: &LightBounds) -> Option<usize> {
+        let centroids = Self::centroid_bounds(bvh_lights);
+        let diagonal = bounds.bbox.max - bounds.bbox.min;
+
+        let mut min_cost = f64::INFINITY;
+        let mut best: Option<(usize, usize)> = None;
+
+        for axis in 0..3 {
+            let (c_min, c_max) = (centroids.min[axis], centroids.max[axis]);
+            if c_max - c_min < 1e-9 {
+                continue;
+            }
+
+            let bucket_of = |b: &LightBounds| {
+                let offset = (b.centroid()[axis] - c_min) / (c_max - c_min);
+                ((NUM_BUCKETS as f64 * offset) as usize).min(NUM_BUCKETS - 1)
+            };
+
+            let mut buckets: Vec<Option<LightBounds>> = vec![None; NUM_BUCKETS];
+            for (_, b) in bvh_lights.iter() {
+                let bucket = &mut buckets[bucket_of(b)];
+                *bucket = Some(match bucket {
+                    Some(acc) => LightBounds::union(acc, b),
+                    None => b.clone(),
+                });
+            }
+
+            let k_r = diagonal.max() / diagonal[axis].max(1e-9);
+
+            for split in 0..NUM_BUCKETS - 1 {
+                let below = Self::union_buckets(&buckets[..=split]);
+                let above = Self::union_buckets(&buckets[split + 1..]);
+                let cost = Self::evaluate_cost(&below, k_r) + Self::evaluate_cost(&above, k_r);
+                if cost > 0.0 && cost < min_cost {
+                    min_cost = cost;
+                    best = Some((axis, split));
+                }
+            }
+        }
+
+        let (axis, split) = best?;
+        let c_min = centroids.min[axis];
+        let c_extent = centroids.max[axis] - c_min;
+        let goes_left = |b: &LightBounds| {
+            let offset = (b.centroid()[axis] - c_min) / c_extent;
+            ((NUM_BUCKETS as f64 * offset) as usize).min(NUM_BUCKETS - 1) <= split
+        };
+
+        bvh_lights.sort_by_key(|(_, b)| !goes_left(b));
+        let mid = bvh_lights.iter().filter(|(_, b)| goes_left(b)).count();
+
+        if mid == 0 || mid == bvh_lights.len() {
+            return None;
+        }
+        Some(mid)
+    }
+
+    fn union_buckets(buckets: &[Option<LightBounds>]) -> Option<LightBounds> {
+        buckets.iter().flatten().fold(None, |acc, b| match acc {
+            Some(acc) => Some(LightBounds::union(&acc, b)),
+            None => Some(b.clone()),
+        })
+    }
+
+    fn evaluate_cost(bounds: &Option<LightBounds>, k_r: f64) -> f64 {
+        let Some(b) = bounds else {
+            return 0.0;
+        };
+
+        let theta_o = b.cos_theta_o.clamp(-1.0, 1.0).acos();
+        let theta_e = b.cos_theta_e.clamp(-1.0, 1.0).acos();
+        let theta_w = (theta_o + theta_e).min(PI);
+        let sin_theta_o = theta_o.sin();
+        let m_omega = 2.0 * PI * (1.0 - b.cos_theta_o)
+            + PI / 2.0
+                * (2.0 * theta_w * sin_theta_o
+                    - (theta_o - 2.0 * theta_w).cos()
+                    - 2.0 * theta_o * sin_theta_o
+                    + b.cos_theta_o);
+
+        b.phi * m_omega * k_r * b.bbox.surface_area()
+    }
+}
+
+impl BvhLightSampler {
+    fn child_probability(&self, p: &Point3, n: &Vec3, left: usize, right: usize) -> Option<f64> {
+        let ci_left = self.nodes[left].bounds().importance(p, n);
+        let ci_right = self.nodes[right].bounds().importance(p, n);
+        if ci_left == 0.0 && ci_right == 0.0 {
+            return None;
+        }
+        Some(ci_left / (ci_left + ci_right))
+    }
+}
+
+impl LightSampler for BvhLightSampler {
+    fn sample(&self, p: &Point3, n: &Vec3, u: f64) -> Option<(usize, f64)> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let mut node_idx = 0;
+        let mut pmf = 1.0;
+        let mut u = u;
+
+        loop {
+            match &self.nodes[node_idx] {
+                LightBvhNode::Leaf(bounds, light_indices) => {
+                    if node_idx == 0 && bounds.importance(p, n) <= 0.0 {
+                        return None;
+                    }
+                    // Lights sharing a leaf are picked uniformly
+                    let count = light_indices.len();
+                    let i = ((u * count as f64) as usize).min(count - 1);
+                    return Some((light_indices[i], pmf / count as f64));
+                }
+                LightBvhNode::Inner(_, left, right) => {
+                    let p_left = self.child_probability(p, n, *left, *right)?;
+                    if u < p_left {
+                        node_idx = *left;
+                        u = (u / p_left).min(1.0 - f64::EPSILON);
+                        pmf *= p_left;
+                    } else {
+                        node_idx = *right;
+                        u = ((u - p_left) / (1.0 - p_left)).min(1.0 - f64::EPSILON);
+                        pmf *= 1.0 - p_left;
+                    }
+                }
+            }
+        }
+    }
+
+    fn pmf(&self, p: &Point3, n: &Vec3, light_idx: usize) -> f64 {
+        let Some(mut bit_trail) = self.bit_trails.get(light_idx).copied().flatten() else {
+            return 0.0;
+        };
+
+        let mut node_idx = 0;
+        let mut pmf = 1.0;
+
+        loop {
+            match &self.nodes[node_idx] {
+                LightBvhNode::Leaf(bounds, light_indices) => {
+                    if node_idx == 0 && bounds.importance(p, n) <= 0.0 {
+                        return 0.0;
+                    }
+                    return pmf / light_indices.len() as f64;
+                }
+                LightBvhNode::Inner(_, left, right) => {
+                    let Some(p_left) = self.child_probability(p, n, *left, *right) else {
+                        return 0.0;
+                    };
+                    if bit_trail & 1 == 0 {
+                        node_idx = *left;
+                        pmf *= p_left;
+                    } else {
+                        node_idx = *right;
+                        pmf *= 1.0 - p_left;
+                    }
+                    bit_trail >>= 1;
+                }
+            }
+        }
+    }
+}
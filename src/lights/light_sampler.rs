@@ -0,0 +1,91 @@
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use crate::lights::{BvhLightSampler, Light};
+use crate::utils::{AliasTable, Point3, Vec3};
+
+pub trait LightSampler: Debug {
+    // Picks a light for a receiver at `p` with normal `n`, returns its index and probability
+    fn sample(&self, p: &Point3, n: &Vec3, u: f64) -> Option<(usize, f64)>;
+
+    fn pmf(&self, p: &Point3, n: &Vec3, light_idx: usize) -> f64;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum LightSampling {
+    Uniform,
+    Power,
+    #[default]
+    Bvh,
+}
+
+impl LightSampling {
+    pub fn build(&self, lights: &[Rc<dyn Light>]) -> Box<dyn LightSampler> {
+        match self {
+            LightSampling::Uniform => Box::new(UniformLightSampler::new(lights)),
+            LightSampling::Power => Box::new(PowerLightSampler::new(lights)),
+            LightSampling::Bvh => Box::new(BvhLightSampler::new(lights)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UniformLightSampler {
+    num_lights: usize,
+}
+
+impl UniformLightSampler {
+    pub fn new(lights: &[Rc<dyn Light>]) -> Self {
+        Self {
+            num_lights: lights.len(),
+        }
+    }
+}
+
+impl LightSampler for UniformLightSampler {
+    fn sample(&self, _: &Point3, _: &Vec3, u: f64) -> Option<(usize, f64)> {
+        if self.num_lights == 0 {
+            return None;
+        }
+
+        let idx = ((u * self.num_lights as f64) as usize).min(self.num_lights - 1);
+        Some((idx, 1.0 / self.num_lights as f64))
+    }
+
+    fn pmf(&self, _: &Point3, _: &Vec3, _: usize) -> f64 {
+        if self.num_lights == 0 {
+            return 0.0;
+        }
+        1.0 / self.num_lights as f64
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PowerLightSampler {
+    table: AliasTable,
+}
+
+impl PowerLightSampler {
+    pub fn new(lights: &[Rc<dyn Light>]) -> Self {
+        let powers: Vec<f64> = lights.iter().map(|light| light.bounds().phi).collect();
+
+        // Fall back to uniform sampling if no light reports any power
+        let table = if powers.iter().any(|&phi| phi > 0.0) {
+            AliasTable::new(&powers)
+        } else {
+            AliasTable::new(&vec![1.0; lights.len()])
+        };
+
+        Self { table }
+    }
+}
+
+impl LightSampler for PowerLightSampler {
+    fn sample(&self, _: &Point3, _: &Vec3, u: f64) -> Option<(usize, f64)> {
+        self.table.sample(u)
+    }
+
+    fn pmf(&self, _: &Point3, _: &Vec3, light_idx: usize) -> f64 {
+        self.table.pmf(light_idx)
+    }
+}
@@ -0,0 +1,18 @@
+pub mod ies;
+pub mod light;
+pub mod light_bounds;
+pub mod light_bvh;
+pub mod light_sampler;
+pub mod point;
+pub mod spot;
+pub mod triangle;
+
+// Export
+pub use ies::*;
+pub use light::*;
+pub use light_bounds::*;
+pub use light_bvh::*;
+pub use light_sampler::*;
+pub use point::*;
+pub use spot::*;
+pub use triangle::*;
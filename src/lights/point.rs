@@ -0,0 +1,95 @@
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::lights::{IesProfile, Light, LightBounds, LightEmission, LightSample};
+use crate::optics::Ray;
+use crate::utils::{Color, ColorExt, Onb, Point3, Sampler, Vec3, Vec3Ext};
+
+#[derive(Debug)]
+pub struct PointLight {
+    position: Point3,
+    intensity: Color,
+    frame: Onb,
+    profile: Option<Rc<IesProfile>>,
+}
+
+impl PointLight {
+    pub fn new(position: Point3, intensity: Color) -> Self {
+        Self {
+            position,
+            intensity,
+            frame: Onb::from_w(&Vec3::new(0.0, -1.0, 0.0)),
+            profile: None,
+        }
+    }
+
+    pub fn from_blackbody(position: Point3, temperature: f64, intensity: f64) -> Self {
+        Self::new(position, Color::blackbody(temperature) * intensity)
+    }
+
+    pub fn from_ies(
+        position: Point3,
+        aim: Vec3,
+        intensity: Color,
+        profile: Rc<IesProfile>,
+    ) -> Self {
+        Self {
+            position,
+            intensity,
+            frame: Onb::from_w(&aim),
+            profile: Some(profile),
+        }
+    }
+}
+
+impl Light for PointLight {
+    fn sample(&self, p: &Point3, _: &mut Sampler) -> Option<LightSample> {
+        let to_light = self.position - p;
+        let distance = to_light.norm();
+        let direction = to_light / distance;
+
+        let factor = match &self.profile {
+            Some(profile) => profile.direction_factor(&self.frame, &-direction),
+            None => 1.0,
+        };
+        if factor <= 0.0 {
+            return None;
+        }
+
+        Some(LightSample {
+            direction,
+            distance,
+            radiance: self.intensity * factor / (distance * distance),
+            pdf: 1.0,
+            normal: Vec3::zeros(),
+        })
+    }
+
+    fn sample_emission(&self, rng: &mut Sampler) -> Option<LightEmission> {
+        let direction = Vec3::random_unit_sphere_vector(rng);
+        let factor = match &self.profile {
+            Some(profile) => profile.direction_factor(&self.frame, &direction),
+            None => 1.0,
+        };
+
+        Some(LightEmission {
+            ray: Ray::new(self.position, direction, 0.0),
+            normal: Vec3::zeros(),
+            radiance: self.intensity * factor,
+            pdf_position: 1.0,
+            pdf_direction: 1.0 / (4.0 * PI),
+        })
+    }
+
+    fn pdf_emission(&self, _: &Point3, _: &Vec3) -> (f64, f64) {
+        (0.0, 1.0 / (4.0 * PI))
+    }
+
+    fn is_delta(&self) -> bool {
+        true
+    }
+
+    fn bounds(&self) -> LightBounds {
+        LightBounds::point(&self.position, 4.0 * PI * self.intensity.luminance())
+    }
+}
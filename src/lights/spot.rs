@@ -0,0 +1,157 @@
+use rand::prelude::Rng;
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::geometry::accel::AABB;
+use crate::lights::{IesProfile, Light, LightBounds, LightEmission, LightSample};
+use crate::optics::Ray;
+use crate::utils::{Color, ColorExt, Onb, Point3, Sampler, Vec3, Vec3Ext};
+
+#[derive(Debug)]
+pub struct SpotLight {
+    position: Point3,
+    intensity: Color,
+    frame: Onb,
+    cos_cone: f64,
+    cos_falloff_start: f64,
+    profile: Option<Rc<IesProfile>>,
+}
+
+impl SpotLight {
+    // Angles in degrees, measured from the spot axis
+    pub fn new(
+        position: Point3,
+        look_at: Point3,
+        intensity: Color,
+        cone_angle: f64,
+        falloff_start: f64,
+    ) -> Self {
+        Self {
+            position,
+            intensity,
+            frame: Onb::from_w(&(look_at - position)),
+            cos_cone: cone_angle.to_radians().cos(),
+            cos_falloff_start: falloff_start.min(cone_angle).to_radians().cos(),
+            profile: None,
+        }
+    }
+
+    pub fn from_blackbody(
+        position: Point3,
+        look_at: Point3,
+        temperature: f64,
+        intensity: f64,
+        cone_angle: f64,
+        falloff_start: f64,
+    ) -> Self {
+        Self::new(
+            position,
+            look_at,
+            Color::blackbody(temperature) * intensity,
+            cone_angle,
+            falloff_start,
+        )
+    }
+
+    pub fn from_ies(
+        position: Point3,
+        look_at: Point3,
+        intensity: Color,
+        cone_angle: f64,
+        falloff_start: f64,
+        profile: Rc<IesProfile>,
+    ) -> Self {
+        Self {
+            profile: Some(profile),
+            ..Self::new(position, look_at, intensity, cone_angle, falloff_start)
+        }
+    }
+}
+
+impl SpotLight {
+    fn falloff(&self, cos_theta: f64) -> f64 {
+        if cos_theta < self.cos_cone {
+            return 0.0;
+        }
+        if cos_theta >= self.cos_falloff_start {
+            return 1.0;
+        }
+
+        let delta = (cos_theta - self.cos_cone) / (self.cos_falloff_start - self.cos_cone);
+        delta.powi(4)
+    }
+
+    fn intensity_towards(&self, direction: &Vec3) -> Color {
+        let mut factor = self.falloff(direction.dot(&self.frame.w()));
+        if let Some(profile) = &self.profile {
+            factor *= profile.direction_factor(&self.frame, direction);
+        }
+        self.intensity * factor
+    }
+}
+
+impl Light for SpotLight {
+    fn sample(&self, p: &Point3, _: &mut Sampler) -> Option<LightSample> {
+        let to_light = self.position - p;
+        let distance = to_light.norm();
+        let direction = to_light / distance;
+
+        let intensity = self.intensity_towards(&-direction);
+        if intensity.near_zero() {
+            return None;
+        }
+
+        Some(LightSample {
+            direction,
+            distance,
+            radiance: intensity / (distance * distance),
+            pdf: 1.0,
+            normal: Vec3::zeros(),
+        })
+    }
+
+    // Uniform over the cone
+    fn sample_emission(&self, rng: &mut Sampler) -> Option<LightEmission> {
+        let cos_theta = 1.0 - rng.gen::<f64>() * (1.0 - self.cos_cone);
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * PI * rng.gen::<f64>();
+        let direction = self.frame.to_world(&Vec3::new(
+            sin_theta * phi.cos(),
+            sin_theta * phi.sin(),
+            cos_theta,
+        ));
+
+        Some(LightEmission {
+            ray: Ray::new(self.position, direction, 0.0),
+            normal: Vec3::zeros(),
+            radiance: self.intensity_towards(&direction),
+            pdf_position: 1.0,
+            pdf_direction: 1.0 / (2.0 * PI * (1.0 - self.cos_cone)),
+        })
+    }
+
+    fn pdf_emission(&self, _: &Point3, direction: &Vec3) -> (f64, f64) {
+        if direction.normalize().dot(&self.frame.w()) < self.cos_cone {
+            return (0.0, 0.0);
+        }
+        (0.0, 1.0 / (2.0 * PI * (1.0 - self.cos_cone)))
+    }
+
+    fn is_delta(&self) -> bool {
+        true
+    }
+
+    fn bounds(&self) -> LightBounds {
+        let theta_cone = self.cos_cone.acos();
+        let theta_falloff_start = self.cos_falloff_start.acos();
+
+        LightBounds::new(
+            AABB::wrap_points(&self.position, &self.position),
+            self.frame.w(),
+            4.0 * PI * self.intensity.luminance(),
+            self.cos_falloff_start,
+            (theta_cone - theta_falloff_start).cos(),
+            false,
+        )
+    }
+}
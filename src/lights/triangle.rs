@@ -0,0 +1,191 @@
+use rand::prelude::Rng;
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::geometry::{accel::AABB, HitRecord};
+use crate::lights::{Light, LightBounds, LightEmission, LightSample};
+use crate::materials::Material;
+use crate::optics::Ray;
+use crate::utils::{ColorExt, Onb, Point3, Sampler, Vec3, Vec3Ext};
+
+// Single emissive triangle of a mesh, sampled uniformly by area
+#[derive(Debug)]
+pub struct TriangleLight {
+    vertices: [Point3; 3],
+    uvs: [Point3; 3],
+    normal: Vec3,
+    area: f64,
+    mat: Rc<dyn Material>,
+}
+
+impl TriangleLight {
+    pub fn new(
+        vertices: [Point3; 3],
+        normals: [Vec3; 3],
+        uvs: [Point3; 3],
+        mat: Rc<dyn Material>,
+    ) -> Self {
+        let n = (vertices[1] - vertices[0]).cross(&(vertices[2] - vertices[0]));
+        let area = 0.5 * n.norm();
+
+        // Orient the geometric normal like the shading normals
+        let mut normal = n.normalize();
+        if normal.dot(&(normals[0] + normals[1] + normals[2])) < 0.0 {
+            normal = -normal;
+        }
+
+        Self {
+            vertices,
+            uvs,
+            normal,
+            area,
+            mat,
+        }
+    }
+}
+
+impl TriangleLight {
+    pub fn area(&self) -> f64 {
+        self.area
+    }
+
+    fn sample_barycentrics(rng: &mut Sampler) -> [f64; 3] {
+        let su0 = rng.gen::<f64>().sqrt();
+        let b0 = 1.0 - su0;
+        let b1 = rng.gen::<f64>() * su0;
+        [b0, b1, 1.0 - b0 - b1]
+    }
+
+    fn surface_record(&self, b: [f64; 3], r: &Ray) -> HitRecord {
+        let mut rec = HitRecord {
+            p: self.vertices[0] * b[0] + self.vertices[1] * b[1] + self.vertices[2] * b[2],
+            u: self.uvs[0].x() * b[0] + self.uvs[1].x() * b[1] + self.uvs[2].x() * b[2],
+            v: self.uvs[0].y() * b[0] + self.uvs[1].y() * b[1] + self.uvs[2].y() * b[2],
+            mat: Rc::clone(&self.mat),
+            ..HitRecord::default()
+        };
+        rec.set_face_normal(r, self.normal);
+        rec
+    }
+}
+
+impl Light for TriangleLight {
+    fn sample(&self, p: &Point3, rng: &mut Sampler) -> Option<LightSample> {
+        if self.area <= 0.0 {
+            return None;
+        }
+
+        let b = Self::sample_barycentrics(rng);
+        let point = self.vertices[0] * b[0] + self.vertices[1] * b[1] + self.vertices[2] * b[2];
+        let to_light = point - p;
+        let distance = to_light.norm();
+        if distance <= 0.0 {
+            return None;
+        }
+        let direction = to_light / distance;
+
+        let cos_light = self.normal.dot(&direction).abs();
+        if cos_light < 1e-8 {
+            return None;
+        }
+
+        let r = Ray::new(*p, direction, 0.0);
+        let mut rec = self.surface_record(b, &r);
+        rec.t = distance;
+
+        let radiance = self.mat.emitted(&r, &rec);
+        if radiance.near_zero() {
+            return None;
+        }
+
+        Some(LightSample {
+            direction,
+            distance,
+            radiance,
+            pdf: distance * distance / (cos_light * self.area),
+            normal: self.normal,
+        })
+    }
+
+    // Uniform by area, cosine weighted around the normal
+    fn sample_emission(&self, rng: &mut Sampler) -> Option<LightEmission> {
+        if self.area <= 0.0 {
+            return None;
+        }
+
+        let b = Self::sample_barycentrics(rng);
+        let point = self.vertices[0] * b[0] + self.vertices[1] * b[1] + self.vertices[2] * b[2];
+
+        let (u1, u2) = (rng.gen::<f64>(), rng.gen::<f64>());
+        let (radius, phi) = (u1.sqrt(), 2.0 * PI * u2);
+        let cos_theta = (1.0 - u1).sqrt();
+        if cos_theta < 1e-8 {
+            return None;
+        }
+        let direction = Onb::from_w(&self.normal).to_world(&Vec3::new(
+            radius * phi.cos(),
+            radius * phi.sin(),
+            cos_theta,
+        ));
+
+        let r = Ray::new(point + direction, -direction, 0.0);
+        let mut rec = self.surface_record(b, &r);
+        rec.t = 1.0;
+
+        Some(LightEmission {
+            ray: Ray::new(point, direction, 0.0),
+            normal: self.normal,
+            radiance: self.mat.emitted(&r, &rec),
+            pdf_position: 1.0 / self.area,
+            pdf_direction: cos_theta / PI,
+        })
+    }
+
+    fn pdf_emission(&self, _: &Point3, direction: &Vec3) -> (f64, f64) {
+        if self.area <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let cos_theta = self.normal.dot(&direction.normalize()).max(0.0);
+        (1.0 / self.area, cos_theta / PI)
+    }
+
+    fn pdf(&self, p: &Point3, light_point: &Point3) -> f64 {
+        let to_light = light_point - p;
+        let distance_squared = to_light.norm_squared();
+        let cos_light = self.normal.dot(&to_light.normalize()).abs();
+        if cos_light < 1e-8 || self.area <= 0.0 {
+            return 0.0;
+        }
+
+        distance_squared / (cos_light * self.area)
+    }
+
+    fn bounds(&self) -> LightBounds {
+        // Conservative emission estimate from the corners and centroid, seen head-on
+        let third = 1.0 / 3.0;
+        let max_luminance = [
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [third, third, third],
+        ]
+        .iter()
+        .map(|&b| {
+            let p = self.vertices[0] * b[0] + self.vertices[1] * b[1] + self.vertices[2] * b[2];
+            let r = Ray::new(p + self.normal, -self.normal, 0.0);
+            self.mat
+                .emitted(&r, &self.surface_record(b, &r))
+                .luminance()
+        })
+        .fold(0.0, f64::max);
+
+        LightBounds::new(
+            AABB::wrap_triangle(&self.vertices),
+            self.normal,
+            PI * self.area * max_luminance,
+            1.0,
+            0.0,
+            false,
+        )
+    }
+}
@@ -6,12 +6,13 @@ use std::sync::mpsc;
 use std::thread;
 
 use lib::examples::example_suzanne as scene;
+use lib::integrators::{Heatmap, IntegratorType};
 use lib::optics::{Camera, RenderOutputConfig};
 use lib::utils::Vec3;
 
 use RenderMode::{Dev, Latest};
 
-fn render(render_mode: RenderMode, timestep: f64) -> RgbImage {
+fn render(render_mode: RenderMode, integrator: IntegratorType, timestep: f64) -> RgbImage {
     let (world, mut viewport_config, lens_config) = scene();
 
     let render_output_config = match render_mode {
@@ -20,12 +21,14 @@ fn render(render_mode: RenderMode, timestep: f64) -> RgbImage {
             image_width: 1920,
             samples_per_pixel: 32,
             max_depth: 32,
+            integrator,
         },
         Latest => RenderOutputConfig {
             aspect_ratio: 16.0 / 9.0,
             image_width: 3840,
             samples_per_pixel: 32,
             max_depth: 32,
+            integrator,
         },
     };
 
@@ -57,6 +60,11 @@ fn main() {
     } else {
         Dev
     };
+    let integrator = if args.len() > 2 {
+        IntegratorType::from_name(&args[2]).expect("Invalid integrator")
+    } else {
+        IntegratorType::default()
+    };
 
     let _output_path = match render_mode {
         Dev => Path::new("images/output.png"),
@@ -73,7 +81,11 @@ fn main() {
             let tx = tx.clone();
             let render_mode = render_mode.clone();
             thread::spawn(move || {
-                let image = render(render_mode, timestep as f64 / num_timesteps as f64);
+                let image = render(
+                    render_mode,
+                    integrator,
+                    timestep as f64 / num_timesteps as f64,
+                );
                 tx.send(image).unwrap();
             });
         }
@@ -82,6 +94,7 @@ fn main() {
         let mut output_image: RgbImage = RgbImage::new(images[0].width(), images[0].height());
 
         Camera::aggregate(&mut output_image, &images);
+        Heatmap::report();
         output_image
             .save(Path::new(&format!("images/output/{}.png", timestep)))
             .unwrap();
@@ -0,0 +1,175 @@
+use std::rc::Rc;
+
+use crate::geometry::HitRecord;
+use crate::materials::Material;
+use crate::optics::{NestedMedium, Ray};
+use crate::textures::Texture;
+use crate::utils::{Color, ColorExt, Onb, Sampler, Vec3, Vec3Ext};
+
+// Offset of the finite differences in UV space, the position moves along the surface with it
+const BUMP_DELTA: f64 = 1e-3;
+
+#[derive(Debug)]
+enum Perturbation {
+    NormalMap { map: Rc<dyn Texture>, strength: f64 },
+    Height { height: Rc<dyn Texture>, scale: f64 },
+}
+
+// Wraps a material with a shading normal perturbed by a tangent space normal map or by the
+// gradient of a height texture. The frame follows the surface tangent, with the bitangent
+// completing it to the right, as in OpenGL normal maps.
+#[derive(Debug)]
+pub struct Bumped {
+    material: Rc<dyn Material>,
+    perturbation: Perturbation,
+}
+
+impl Bumped {
+    // Normal map in [0, 1], flat is (0.5, 0.5, 1) like in most tangent space maps
+    pub fn normal_map(material: Rc<dyn Material>, map: Rc<dyn Texture>) -> Self {
+        Self {
+            material,
+            perturbation: Perturbation::NormalMap { map, strength: 1.0 },
+        }
+    }
+
+    // Gray level heights, where `scale` is the displacement in world units of a unit gray level
+    pub fn height_map(material: Rc<dyn Material>, height: Rc<dyn Texture>, scale: f64) -> Self {
+        Self {
+            material,
+            perturbation: Perturbation::Height { height, scale },
+        }
+    }
+
+    // Flattens or exaggerates the slopes of a normal map
+    pub fn with_strength(mut self, strength: f64) -> Self {
+        if let Perturbation::NormalMap { strength: s, .. } = &mut self.perturbation {
+            *s = strength;
+        }
+        self
+    }
+}
+
+impl Bumped {
+    // Hit record with the perturbed shading normal, kept on the side of the incoming ray
+    fn perturbed(&self, r_in: &Ray, rec: &HitRecord) -> HitRecord {
+        // Perturb the outward side so that both faces agree on the relief
+        let outward = if rec.front_face {
+            rec.normal
+        } else {
+            -rec.normal
+        };
+        let frame = Onb::from_wu(&outward, &rec.tangent);
+
+        let normal = match &self.perturbation {
+            Perturbation::NormalMap { map, strength } => {
+                let n = 2.0 * map.sample(rec.u, rec.v, &rec.p) - Color::ones();
+                frame.to_world(&Vec3::new(
+                    n.x() * strength,
+                    n.y() * strength,
+                    n.z().max(0.0),
+                ))
+            }
+            Perturbation::Height { height, scale } => {
+                // Surface derivatives, a unit frame where the primitive does not provide them
+                let (dp_du, dp_dv) = if rec.tangent.near_zero() || rec.bitangent.near_zero() {
+                    (frame.u(), frame.v())
+                } else {
+                    (rec.tangent, rec.bitangent)
+                };
+                let h = |du: f64, dv: f64| {
+                    let p = rec.p + BUMP_DELTA * (du * dp_du + dv * dp_dv);
+                    let (u, v) = (rec.u + BUMP_DELTA * du, rec.v + BUMP_DELTA * dv);
+                    height.sample(u, v, &p).luminance()
+                };
+                let h0 = h(0.0, 0.0);
+                let dh_du = (h(1.0, 0.0) - h0) / BUMP_DELTA;
+                let dh_dv = (h(0.0, 1.0) - h0) / BUMP_DELTA;
+
+                // Normal of the surface displaced along the outward normal (Blinn)
+                let normal =
+                    (dp_du + *scale * dh_du * outward).cross(&(dp_dv + *scale * dh_dv * outward));
+                if normal.dot(&outward) < 0.0 {
+                    -normal
+                } else {
+                    normal
+                }
+            }
+        };
+
+        let mut perturbed = rec.clone();
+        if normal.near_zero() {
+            return perturbed;
+        }
+        let normal = normal.normalize();
+        let normal = if rec.front_face { normal } else { -normal };
+
+        // Keep the original normal where the relief would face away from the viewer
+        if normal.dot(&r_in.direction()) < 0.0 {
+            perturbed.normal = normal;
+        }
+        perturbed
+    }
+
+    // Directions must lie on the same side of both normals, otherwise light leaks through
+    fn consistent(rec: &HitRecord, perturbed: &HitRecord, direction: &Vec3) -> bool {
+        (rec.normal.dot(direction) > 0.0) == (perturbed.normal.dot(direction) > 0.0)
+    }
+}
+
+impl Material for Bumped {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        rng: &mut Sampler,
+        attenuation: &mut Vec3,
+        scattered: &mut Ray,
+    ) -> bool {
+        let perturbed = self.perturbed(r_in, rec);
+        self.material
+            .scatter(r_in, &perturbed, rng, attenuation, scattered)
+            && Self::consistent(rec, &perturbed, &scattered.direction())
+    }
+
+    fn is_emissive(&self) -> bool {
+        self.material.is_emissive()
+    }
+
+    fn is_specular(&self) -> bool {
+        self.material.is_specular()
+    }
+
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        self.material.emitted(r_in, rec)
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> Color {
+        let perturbed = self.perturbed(r_in, rec);
+        if !Self::consistent(rec, &perturbed, direction) {
+            return Color::zeros();
+        }
+        self.material.eval(r_in, &perturbed, direction)
+    }
+
+    fn pdf(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> f64 {
+        let perturbed = self.perturbed(r_in, rec);
+        if !Self::consistent(rec, &perturbed, direction) {
+            return 0.0;
+        }
+        self.material.pdf(r_in, &perturbed, direction)
+    }
+
+    fn opacity(&self, u: f64, v: f64, p: &Vec3) -> f64 {
+        self.material.opacity(u, v, p)
+    }
+
+    fn media_towards(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        direction: &Vec3,
+    ) -> Option<Vec<NestedMedium>> {
+        self.material.media_towards(r_in, rec, direction)
+    }
+}
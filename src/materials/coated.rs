@@ -0,0 +1,229 @@
+use rand::Rng;
+use std::rc::Rc;
+
+use crate::geometry::HitRecord;
+use crate::materials::{fresnel_dielectric, Material, TrowbridgeReitz};
+use crate::optics::{NestedMedium, Ray};
+use crate::textures::{Solid, Texture};
+use crate::utils::{Color, ColorExt, Onb, Sampler, Vec3, Vec3Ext};
+
+// Dielectric coat over a base material, like car paint or varnished wood. The coat reflects with
+// a GGX microfacet distribution, what it lets through reaches the base and back losing the
+// Fresnel transmittance and the absorption along both refracted paths (Weidlich and Wilkie,
+// without interreflections inside the layer). Light the base transmits crosses the coat once, on
+// the outer side.
+#[derive(Debug)]
+pub struct Coated {
+    base: Rc<dyn Material>,
+    refraction_index: f64,
+    roughness: Rc<dyn Texture>, // Gray level, near mirror-like at zero
+    thickness: f64,
+    absorption: Color, // Beer-Lambert coefficients per unit length, clear if zero
+}
+
+impl Coated {
+    pub fn new(base: Rc<dyn Material>, refraction_index: f64) -> Self {
+        Self {
+            base,
+            refraction_index,
+            roughness: Rc::new(Solid::new(Color::zeros())),
+            thickness: 0.01,
+            absorption: Color::zeros(),
+        }
+    }
+
+    pub fn with_roughness(mut self, roughness: Rc<dyn Texture>) -> Self {
+        self.roughness = roughness;
+        self
+    }
+
+    pub fn with_thickness(mut self, thickness: f64) -> Self {
+        self.thickness = thickness.max(0.0);
+        self
+    }
+
+    pub fn with_absorption(mut self, absorption: Color) -> Self {
+        self.absorption = absorption.map(|a| a.max(0.0));
+        self
+    }
+
+    // Tinted such that light keeps `color` after crossing the coat once at normal incidence
+    pub fn with_tint(self, color: Color) -> Self {
+        let thickness = self.thickness.max(1e-6);
+        let absorption = color.map(|c| -c.max(1e-6).ln() / thickness);
+        self.with_absorption(absorption)
+    }
+}
+
+impl Coated {
+    fn distribution(&self, rec: &HitRecord) -> TrowbridgeReitz {
+        let roughness = self.roughness.sample(rec.u, rec.v, &rec.p).luminance();
+        TrowbridgeReitz::from_roughness(roughness.clamp(0.0, 1.0))
+    }
+
+    // Chance of sampling the coat rather than the base, its reflectance along the macro normal.
+    // Seen from inside a transmissive base the coat is behind the surface.
+    fn coat_probability(&self, rec: &HitRecord, wo: &Vec3) -> f64 {
+        if !rec.front_face {
+            return 0.0;
+        }
+        fresnel_dielectric(wo.z(), self.refraction_index).clamp(0.05, 0.95)
+    }
+
+    // Fresnel transmittance and absorption of one crossing at `cos` from the normal
+    fn crossing(&self, cos: f64) -> Color {
+        let sin2 = (1.0 - cos * cos) / (self.refraction_index * self.refraction_index);
+        let refracted_cos = (1.0 - sin2).max(0.0).sqrt().max(1e-4);
+        let absorbed = (-self.absorption * self.thickness / refracted_cos).map(f64::exp);
+        absorbed * (1.0 - fresnel_dielectric(cos, self.refraction_index))
+    }
+
+    // What passes the coat on the way from `wo` to `wi` through the base, crossing it for each
+    // direction on the outer side
+    fn transmittance(&self, rec: &HitRecord, wo: &Vec3, wi: &Vec3) -> Color {
+        let outside = if rec.front_face { 1.0 } else { -1.0 };
+        [wo, wi]
+            .iter()
+            .filter(|w| w.z() * outside > 0.0)
+            .fold(Color::ones(), |acc, w| {
+                acc.component_mul(&self.crossing(w.z().abs()))
+            })
+    }
+
+    // Coat reflection, BRDF times cosine
+    fn coat_eval(&self, distribution: &TrowbridgeReitz, wo: &Vec3, wi: &Vec3) -> f64 {
+        let wm = (wo + wi).normalize();
+        let fresnel = fresnel_dielectric(wo.dot(&wm), self.refraction_index);
+        fresnel * distribution.d(&wm) * distribution.g(wo, wi) / (4.0 * wo.z())
+    }
+
+    fn coat_pdf(distribution: &TrowbridgeReitz, wo: &Vec3, wi: &Vec3) -> f64 {
+        let wm = (wo + wi).normalize();
+        distribution.visible_d(wo, &wm) / (4.0 * wo.dot(&wm))
+    }
+}
+
+impl Material for Coated {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        rng: &mut Sampler,
+        attenuation: &mut Vec3,
+        scattered: &mut Ray,
+    ) -> bool {
+        let onb = Onb::from_w(&rec.normal);
+        let wo = onb.to_local(&-r_in.direction());
+        if wo.z() <= 0.0 {
+            return false;
+        }
+
+        let distribution = self.distribution(rec);
+        let coat_probability = self.coat_probability(rec, &wo);
+        let base_specular = self.base.is_specular();
+
+        if rng.gen::<f64>() < coat_probability {
+            let wm = distribution.sample_wm(&wo, rng);
+            let wi = (-wo).reflect(&wm);
+            if wi.z() <= 0.0 {
+                return false;
+            }
+
+            let direction = onb.to_world(&wi);
+            *scattered = r_in.scattered(rec.p, direction);
+            *attenuation = if base_specular {
+                // The base has no density to share with
+                let pdf = coat_probability * Self::coat_pdf(&distribution, &wo, &wi);
+                Color::constant(self.coat_eval(&distribution, &wo, &wi) / pdf)
+            } else {
+                self.eval(r_in, rec, &direction) / self.pdf(r_in, rec, &direction)
+            };
+            return true;
+        }
+
+        let mut base_attenuation = Color::zeros();
+        if !self
+            .base
+            .scatter(r_in, rec, rng, &mut base_attenuation, scattered)
+        {
+            return false;
+        }
+        let direction = scattered.direction();
+        let wi = onb.to_local(&direction);
+
+        *attenuation = if base_specular {
+            base_attenuation.component_mul(&self.transmittance(rec, &wo, &wi))
+                / (1.0 - coat_probability)
+        } else {
+            self.eval(r_in, rec, &direction) / self.pdf(r_in, rec, &direction)
+        };
+        true
+    }
+
+    fn is_emissive(&self) -> bool {
+        self.base.is_emissive()
+    }
+
+    // Without next event estimation through a specular base, the coat is sampled on its own
+    fn is_specular(&self) -> bool {
+        self.base.is_specular()
+    }
+
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        let emitted = self.base.emitted(r_in, rec);
+        if emitted.near_zero() {
+            return emitted;
+        }
+
+        let onb = Onb::from_w(&rec.normal);
+        let wo = onb.to_local(&-r_in.direction());
+        emitted.component_mul(&self.crossing(wo.z()))
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> Color {
+        let onb = Onb::from_w(&rec.normal);
+        let wo = onb.to_local(&-r_in.direction());
+        let wi = onb.to_local(direction);
+        if wo.z() <= 0.0 {
+            return Color::zeros();
+        }
+
+        let base = self
+            .base
+            .eval(r_in, rec, direction)
+            .component_mul(&self.transmittance(rec, &wo, &wi));
+        if !rec.front_face || wi.z() <= 0.0 {
+            return base;
+        }
+        Color::constant(self.coat_eval(&self.distribution(rec), &wo, &wi)) + base
+    }
+
+    fn pdf(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> f64 {
+        let onb = Onb::from_w(&rec.normal);
+        let wo = onb.to_local(&-r_in.direction());
+        let wi = onb.to_local(direction);
+        if wo.z() <= 0.0 {
+            return 0.0;
+        }
+
+        let coat_probability = self.coat_probability(rec, &wo);
+        let base = (1.0 - coat_probability) * self.base.pdf(r_in, rec, direction);
+        if coat_probability <= 0.0 || wi.z() <= 0.0 {
+            return base;
+        }
+        coat_probability * Self::coat_pdf(&self.distribution(rec), &wo, &wi) + base
+    }
+
+    fn opacity(&self, u: f64, v: f64, p: &Vec3) -> f64 {
+        self.base.opacity(u, v, p)
+    }
+
+    fn media_towards(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        direction: &Vec3,
+    ) -> Option<Vec<NestedMedium>> {
+        self.base.media_towards(r_in, rec, direction)
+    }
+}
@@ -0,0 +1,184 @@
+use nalgebra::Complex;
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::geometry::HitRecord;
+use crate::materials::{channel_at, fresnel_conductor, Material, ThinFilm, TrowbridgeReitz};
+use crate::optics::Ray;
+use crate::textures::Texture;
+use crate::utils::{Color, ColorExt, Onb, Sampler, Vec3, Vec3Ext};
+
+// Rough metal with a GGX microfacet distribution, reflecting according to its complex index of
+// refraction. Eta and k are given at the red, green and blue primaries.
+#[derive(Debug)]
+pub struct Conductor {
+    eta: Color,
+    k: Color,
+    distribution: TrowbridgeReitz,
+    rotation: Option<Rc<dyn Texture>>, // Gray level, a full turn of the tangent at one
+    thin_film: Option<ThinFilm>,
+}
+
+impl Conductor {
+    pub fn new(eta: Color, k: Color, roughness: f64) -> Self {
+        Self {
+            eta,
+            k,
+            distribution: TrowbridgeReitz::from_roughness(roughness),
+            rotation: None,
+            thin_film: None,
+        }
+    }
+
+    // Brushed along the surface tangent, see `TrowbridgeReitz::with_anisotropy`
+    pub fn with_anisotropy(mut self, anisotropy: f64) -> Self {
+        self.distribution = self.distribution.with_anisotropy(anisotropy);
+        self
+    }
+
+    // Turns the direction of anisotropy around the normal, e.g. for radially brushed discs
+    pub fn with_rotation(mut self, rotation: Rc<dyn Texture>) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    // Oxide layer or oil slick, tempered steel and the like
+    pub fn with_thin_film(mut self, thin_film: ThinFilm) -> Self {
+        self.thin_film = Some(thin_film);
+        self
+    }
+
+    pub fn gold(roughness: f64) -> Self {
+        Self::new(
+            Color::new(0.143, 0.374, 1.442),
+            Color::new(3.983, 2.386, 1.603),
+            roughness,
+        )
+    }
+
+    pub fn silver(roughness: f64) -> Self {
+        Self::new(
+            Color::new(0.155, 0.117, 0.138),
+            Color::new(4.828, 3.122, 2.147),
+            roughness,
+        )
+    }
+
+    pub fn copper(roughness: f64) -> Self {
+        Self::new(
+            Color::new(0.200, 0.924, 1.102),
+            Color::new(3.912, 2.452, 2.142),
+            roughness,
+        )
+    }
+
+    pub fn aluminium(roughness: f64) -> Self {
+        Self::new(
+            Color::new(1.657, 0.880, 0.521),
+            Color::new(9.224, 6.270, 4.837),
+            roughness,
+        )
+    }
+
+    pub fn chrome(roughness: f64) -> Self {
+        Self::new(
+            Color::new(3.181, 3.181, 2.323),
+            Color::new(3.329, 3.329, 3.135),
+            roughness,
+        )
+    }
+}
+
+impl Default for Conductor {
+    fn default() -> Self {
+        Self::aluminium(0.3)
+    }
+}
+
+impl Conductor {
+    fn frame(&self, rec: &HitRecord) -> Onb {
+        let frame = rec.shading_frame();
+        let Some(rotation) = &self.rotation else {
+            return frame;
+        };
+
+        let angle = 2.0 * PI * rotation.sample(rec.u, rec.v, &rec.p).luminance();
+        let tangent = frame.to_world(&Vec3::new(angle.cos(), angle.sin(), 0.0));
+        Onb::from_wu(&rec.normal, &tangent)
+    }
+
+    // Reflectance from air, through the film if there is one
+    fn fresnel(&self, rec: &HitRecord, cos_theta_i: f64) -> Color {
+        let Some(thin_film) = &self.thin_film else {
+            return fresnel_conductor(cos_theta_i, &self.eta, &self.k);
+        };
+        thin_film.reflectance_rgb(rec, cos_theta_i, 1.0, |lambda| {
+            Complex::new(channel_at(&self.eta, lambda), channel_at(&self.k, lambda))
+        })
+    }
+}
+
+impl Material for Conductor {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        rng: &mut Sampler,
+        attenuation: &mut Vec3,
+        scattered: &mut Ray,
+    ) -> bool {
+        let onb = self.frame(rec);
+        let wo = onb.to_local(&-r_in.direction());
+        if wo.z() <= 0.0 {
+            return false;
+        }
+
+        if self.distribution.effectively_smooth() {
+            let wi = Vec3::new(-wo.x(), -wo.y(), wo.z());
+            *scattered = r_in.scattered(rec.p, onb.to_world(&wi));
+            *attenuation = self.fresnel(rec, wo.z());
+            return true;
+        }
+
+        let wm = self.distribution.sample_wm(&wo, rng);
+        let wi = (-wo).reflect(&wm);
+        if wi.z() <= 0.0 {
+            return false;
+        }
+
+        // f * cos / pdf, with most terms cancelling against the visible normal density
+        let fresnel = self.fresnel(rec, wo.dot(&wm));
+        *scattered = r_in.scattered(rec.p, onb.to_world(&wi));
+        *attenuation = fresnel * self.distribution.g(&wo, &wi) / self.distribution.g1(&wo);
+        true
+    }
+
+    fn is_specular(&self) -> bool {
+        self.distribution.effectively_smooth()
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> Color {
+        let onb = self.frame(rec);
+        let wo = onb.to_local(&-r_in.direction());
+        let wi = onb.to_local(direction);
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return Color::zeros();
+        }
+
+        let wm = (wo + wi).normalize();
+        let fresnel = self.fresnel(rec, wo.dot(&wm));
+        fresnel * self.distribution.d(&wm) * self.distribution.g(&wo, &wi) / (4.0 * wo.z())
+    }
+
+    fn pdf(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> f64 {
+        let onb = self.frame(rec);
+        let wo = onb.to_local(&-r_in.direction());
+        let wi = onb.to_local(direction);
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return 0.0;
+        }
+
+        let wm = (wo + wi).normalize();
+        self.distribution.visible_d(&wo, &wm) / (4.0 * wo.dot(&wm))
+    }
+}
@@ -0,0 +1,85 @@
+use std::rc::Rc;
+
+use crate::geometry::HitRecord;
+use crate::materials::Material;
+use crate::optics::{NestedMedium, Ray};
+use crate::textures::Texture;
+use crate::utils::{Color, ColorExt, Sampler, Vec3};
+
+// Wraps a material with an opacity texture, read as a gray level, for leaves and fences. Triangles
+// and quads skip hits where it is transparent, partially opaque ones stochastically unless a
+// threshold makes every texel either solid or empty.
+#[derive(Debug)]
+pub struct Cutout {
+    material: Rc<dyn Material>,
+    opacity: Rc<dyn Texture>,
+    threshold: Option<f64>,
+}
+
+impl Cutout {
+    pub fn new(material: Rc<dyn Material>, opacity: Rc<dyn Texture>) -> Self {
+        Self {
+            material,
+            opacity,
+            threshold: None,
+        }
+    }
+
+    // Opaque from `threshold` on and fully transparent below, e.g. 0.5 for alpha tested foliage
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+}
+
+impl Material for Cutout {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        rng: &mut Sampler,
+        attenuation: &mut Vec3,
+        scattered: &mut Ray,
+    ) -> bool {
+        self.material
+            .scatter(r_in, rec, rng, attenuation, scattered)
+    }
+
+    fn is_emissive(&self) -> bool {
+        self.material.is_emissive()
+    }
+
+    fn is_specular(&self) -> bool {
+        self.material.is_specular()
+    }
+
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        self.material.emitted(r_in, rec)
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> Color {
+        self.material.eval(r_in, rec, direction)
+    }
+
+    fn pdf(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> f64 {
+        self.material.pdf(r_in, rec, direction)
+    }
+
+    fn opacity(&self, u: f64, v: f64, p: &Vec3) -> f64 {
+        let opacity = self.opacity.sample(u, v, p).luminance().clamp(0.0, 1.0);
+        match self.threshold {
+            Some(threshold) if opacity >= threshold => self.material.opacity(u, v, p),
+            Some(_) => 0.0,
+            None => opacity * self.material.opacity(u, v, p),
+        }
+    }
+
+    fn media_towards(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        direction: &Vec3,
+    ) -> Option<Vec<NestedMedium>> {
+        self.material.media_towards(r_in, rec, direction)
+    }
+}
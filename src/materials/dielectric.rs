@@ -1,29 +1,88 @@
-use rand::prelude::{Rng, ThreadRng};
+use nalgebra::Complex;
+use rand::prelude::Rng;
+use std::rc::Rc;
 
 use crate::geometry::HitRecord;
-use crate::materials::Material;
-use crate::optics::Ray;
-use crate::utils::{Vec3, Vec3Ext};
+use crate::materials::{fresnel_dielectric, Material, ThinFilm, TrowbridgeReitz};
+use crate::optics::{NestedMedium, Ray};
+use crate::textures::{Solid, Texture};
+use crate::utils::{Color, ColorExt, Dispersion, Onb, Sampler, Vec3, Vec3Ext};
 
 #[derive(Debug)]
 pub struct Dielectric {
     refraction_index: f64,
-    frost: f64,
+    roughness: Option<Rc<dyn Texture>>, // Gray level, perfectly smooth if there is none
+    priority: u32, // Where media overlap, the one with the highest priority is present
+    absorption: Color, // Beer-Lambert coefficients per unit length, clear if zero
+    dispersion: Option<Dispersion>, // Only used in spectral mode
+    thin_film: Option<ThinFilm>,
+}
+
+// Both sides of a surface hit, as seen from the incoming ray
+struct Interface {
+    inside: Vec<NestedMedium>,
+    outside: Vec<NestedMedium>,
+    refraction_ratio: f64, // Incident over transmitted index
+    incident_index: f64,
+    dispersive: bool,
 }
 
 impl Dielectric {
     pub fn new(refraction_index: f64) -> Self {
         Self {
             refraction_index,
-            frost: 0.0,
+            roughness: None,
+            priority: 0,
+            absorption: Color::zeros(),
+            dispersion: None,
+            thin_film: None,
+        }
+    }
+
+    // Frosted glass, see `TrowbridgeReitz::from_roughness`. Specular if effectively smooth.
+    pub fn rough(refraction_index: f64, roughness: f64) -> Self {
+        if TrowbridgeReitz::from_roughness(roughness).effectively_smooth() {
+            return Self::new(refraction_index);
         }
+        Self::new(refraction_index).with_roughness(Rc::new(Solid::new(Color::constant(roughness))))
     }
-    pub fn frosted(refraction_index: f64, frost: f64) -> Self {
+
+    // Fixed at the sodium D line outside of spectral mode
+    pub fn dispersive(dispersion: Dispersion) -> Self {
         Self {
-            refraction_index,
-            frost: if frost < 1.0 { frost } else { 1.0 },
+            dispersion: Some(dispersion),
+            ..Self::new(dispersion.refraction_index(589.3))
         }
     }
+
+    pub fn with_roughness(mut self, roughness: Rc<dyn Texture>) -> Self {
+        self.roughness = Some(roughness);
+        self
+    }
+
+    // E.g. a glass container over the liquid it holds, so the liquid may overlap into its walls
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_absorption(mut self, absorption: Color) -> Self {
+        self.absorption = absorption.map(|a| a.max(0.0));
+        self
+    }
+
+    // Tinted such that light keeps `color` after travelling `distance` through the medium
+    pub fn with_transmittance(self, color: Color, distance: f64) -> Self {
+        let distance = distance.max(1e-6);
+        let absorption = color.map(|c| -c.max(1e-6).ln() / distance);
+        self.with_absorption(absorption)
+    }
+
+    // Iridescent coating on the outside, like a soap bubble
+    pub fn with_thin_film(mut self, thin_film: ThinFilm) -> Self {
+        self.thin_film = Some(thin_film);
+        self
+    }
 }
 
 impl Default for Dielectric {
@@ -33,10 +92,169 @@ impl Default for Dielectric {
 }
 
 impl Dielectric {
-    fn reflectance(cosine: f64, ref_idx: f64) -> f64 {
-        let r0 = (1.0 - ref_idx) / (1.0 + ref_idx);
-        let r0 = r0 * r0;
-        r0 + (1.0 - r0) * (1.0 - cosine).powi(5)
+    fn as_medium(&self) -> NestedMedium {
+        NestedMedium {
+            id: self as *const Self as usize,
+            refraction_index: self.refraction_index,
+            priority: self.priority,
+            absorption: self.absorption,
+            dispersion: self.dispersion,
+        }
+    }
+
+    // Medium present where `media` overlap, the innermost one wins among equal priorities
+    fn dominant(media: &[NestedMedium]) -> Option<&NestedMedium> {
+        media.iter().max_by_key(|medium| medium.priority)
+    }
+
+    // Err where the surface lies within a medium of higher priority, so is not an interface at all
+    fn interface(&self, r_in: &Ray, rec: &HitRecord) -> Result<Interface, Interface> {
+        let medium = self.as_medium();
+
+        // Media on both sides of the surface, air if there are none
+        let inside: Vec<NestedMedium> = if rec.front_face {
+            r_in.media().iter().copied().chain([medium]).collect()
+        } else {
+            r_in.media().to_vec()
+        };
+        let mut outside = inside.clone();
+        if let Some(idx) = outside.iter().rposition(|m| m.id == medium.id) {
+            outside.remove(idx);
+        }
+
+        let mut interface = Interface {
+            inside,
+            outside,
+            refraction_ratio: 1.0,
+            incident_index: 1.0,
+            dispersive: false,
+        };
+        let dominant_outside = Self::dominant(&interface.outside).copied();
+        if dominant_outside.is_some_and(|m| m.priority > self.priority) {
+            return Err(interface);
+        }
+
+        let wavelengths = r_in.wavelengths();
+        let index = medium.refraction_index_at(wavelengths);
+        let outside_index = dominant_outside.map_or(1.0, |m| m.refraction_index_at(wavelengths));
+        interface.incident_index = if rec.front_face { outside_index } else { index };
+        interface.refraction_ratio = if rec.front_face {
+            outside_index / index
+        } else {
+            index / outside_index
+        };
+
+        // Each wavelength takes its own direction where either side disperses
+        interface.dispersive =
+            self.dispersion.is_some() || dominant_outside.is_some_and(|m| m.dispersion.is_some());
+
+        Ok(interface)
+    }
+
+    // None where the surface is smooth enough to be treated as a perfect interface
+    fn distribution(&self, rec: &HitRecord) -> Option<TrowbridgeReitz> {
+        let roughness = self.roughness.as_ref()?;
+        let roughness = roughness.sample(rec.u, rec.v, &rec.p).luminance();
+        let distribution = TrowbridgeReitz::from_roughness(roughness);
+        (!distribution.effectively_smooth()).then_some(distribution)
+    }
+
+    // Reflectance at a microfacet or the surface, colored by a thin film
+    fn fresnel(&self, rec: &HitRecord, interface: &Interface, cos_theta_i: f64) -> Color {
+        let eta = 1.0 / interface.refraction_ratio;
+        let Some(thin_film) = &self.thin_film else {
+            return Color::constant(fresnel_dielectric(cos_theta_i, eta));
+        };
+        if fresnel_dielectric(cos_theta_i, eta) >= 1.0 {
+            return Color::ones();
+        }
+
+        let substrate = Complex::new(interface.incident_index * eta, 0.0);
+        thin_film.reflectance_rgb(rec, cos_theta_i, interface.incident_index, |_| substrate)
+    }
+
+    // Media on the side of `direction`
+    fn media_on_side(
+        r_in: &Ray,
+        rec: &HitRecord,
+        direction: &Vec3,
+        interface: Interface,
+    ) -> Vec<NestedMedium> {
+        if direction.dot(&rec.normal) > 0.0 {
+            r_in.media().to_vec()
+        } else if rec.front_face {
+            interface.inside
+        } else {
+            interface.outside
+        }
+    }
+
+    // Continues the path on the side of `direction`, keeping only the hero wavelength if dispersed
+    fn continue_path(r_in: &Ray, rec: &HitRecord, direction: Vec3, interface: Interface) -> Ray {
+        let dispersive = interface.dispersive;
+        let media = Self::media_on_side(r_in, rec, &direction, interface);
+
+        let mut next = r_in.scattered(rec.p, direction).with_media(media);
+        if let Some(mut wavelengths) = r_in.wavelengths().copied().filter(|_| dispersive) {
+            wavelengths.terminate_secondary();
+            next = next.with_wavelengths(wavelengths);
+        }
+        next
+    }
+}
+
+// Rough interfaces follow Walter et al. 2007 with the GGX distribution. Radiance is not scaled by
+// the squared index ratio on refraction, the same as for smooth interfaces.
+impl Dielectric {
+    // Generalized half vector, on the side of `wo` which is above the local surface
+    fn half_vector(wo: &Vec3, wi: &Vec3, eta: f64) -> Option<Vec3> {
+        let wm = if wi.z() > 0.0 { wo + wi } else { wo + wi * eta };
+        if wm.norm_squared() == 0.0 {
+            return None;
+        }
+        let wm = wm.normalize();
+        let wm = if wm.z() < 0.0 { -wm } else { wm };
+
+        // Microfacets facing away from either direction
+        if wm.dot(wo) <= 0.0 || wm.dot(wi) * wi.z() <= 0.0 {
+            return None;
+        }
+        Some(wm)
+    }
+
+    // BSDF times cosine and density, reflections are picked by the mean of the reflectance
+    fn rough_eval(
+        &self,
+        rec: &HitRecord,
+        interface: &Interface,
+        distribution: &TrowbridgeReitz,
+        wo: &Vec3,
+        wi: &Vec3,
+    ) -> (Color, f64) {
+        let eta = 1.0 / interface.refraction_ratio;
+        let Some(wm) = Self::half_vector(wo, wi, eta) else {
+            return (Color::zeros(), 0.0);
+        };
+        let reflectance = self.fresnel(rec, interface, wo.dot(&wm));
+        let reflect_probability = reflectance.mean();
+        let d = distribution.d(&wm);
+        let g = distribution.g(wo, wi);
+
+        if wi.z() > 0.0 {
+            let f = reflectance * d * g / (4.0 * wo.z() * wi.z());
+            let pdf = distribution.visible_d(wo, &wm) / (4.0 * wo.dot(&wm)) * reflect_probability;
+            (f * wi.z(), pdf)
+        } else {
+            let denom = (wi.dot(&wm) + wo.dot(&wm) / eta).powi(2);
+            let transmittance = Color::ones() - reflectance;
+            let f = transmittance
+                * d
+                * g
+                * (wi.dot(&wm) * wo.dot(&wm) / (wi.z() * wo.z() * denom)).abs();
+            let pdf = distribution.visible_d(wo, &wm) * wi.dot(&wm).abs() / denom
+                * (1.0 - reflect_probability);
+            (f * wi.z().abs(), pdf)
+        }
     }
 }
 
@@ -45,35 +263,119 @@ impl Material for Dielectric {
         &self,
         r_in: &Ray,
         rec: &HitRecord,
-        rng: &mut ThreadRng,
+        rng: &mut Sampler,
         attenuation: &mut Vec3,
         scattered: &mut Ray,
     ) -> bool {
-        *attenuation = Vec3::new(1.0, 1.0, 1.0);
-        let refraction_ratio = if rec.front_face {
-            1.0 / self.refraction_index
-        } else {
-            self.refraction_index
+        let interface = match self.interface(r_in, rec) {
+            Ok(interface) => interface,
+            Err(interface) => {
+                // Straight through, entering or leaving this medium without refracting
+                *attenuation = Color::ones();
+                let media = if rec.front_face {
+                    interface.inside
+                } else {
+                    interface.outside
+                };
+                *scattered = r_in.scattered(rec.p, r_in.direction()).with_media(media);
+                return true;
+            }
         };
+        *attenuation = Color::ones();
+        let refraction_ratio = interface.refraction_ratio;
 
-        let cos_theta = (-r_in.direction()).dot(&rec.normal).min(1.0);
-        let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+        let Some(distribution) = self.distribution(rec) else {
+            // Pick by the mean reflectance and weight the colors against it, which is one where
+            // all of the light reflects
+            let cos_theta = (-r_in.direction()).dot(&rec.normal).min(1.0);
+            let reflectance = self.fresnel(rec, &interface, cos_theta);
+            let reflect_probability = reflectance.mean();
 
-        let cannot_refract = refraction_ratio * sin_theta > 1.0;
+            let direction = if reflect_probability > rng.gen::<f64>() {
+                *attenuation = reflectance / reflect_probability;
+                r_in.direction().reflect(&rec.normal)
+            } else {
+                *attenuation = (Color::ones() - reflectance) / (1.0 - reflect_probability);
+                r_in.direction().refract(&rec.normal, refraction_ratio)
+            };
 
-        let direction = if cannot_refract
-            || Self::reflectance(cos_theta, refraction_ratio) > rng.gen::<f64>()
-        {
-            r_in.direction().reflect(&rec.normal)
+            *scattered = Self::continue_path(r_in, rec, direction, interface);
+            return true;
+        };
+
+        let onb = Onb::from_w(&rec.normal);
+        let wo = onb.to_local(&-r_in.direction());
+        if wo.z() <= 0.0 {
+            return false;
+        }
+
+        // Reflect or refract on a visible microfacet, by its mean Fresnel reflectance
+        let wm = distribution.sample_wm(&wo, rng);
+        let eta = 1.0 / refraction_ratio;
+        let reflectance = self.fresnel(rec, &interface, wo.dot(&wm));
+        let reflect_probability = reflectance.mean();
+        let reflect = reflect_probability > rng.gen::<f64>();
+        let wi = if reflect {
+            (-wo).reflect(&wm)
         } else {
-            r_in.direction().refract(&rec.normal, refraction_ratio)
+            (-wo).refract(&wm, refraction_ratio)
         };
 
-        *scattered = Ray::new(
-            rec.p,
-            direction + self.frost * Vec3::random_unit_sphere_vector(rng),
-            r_in.time(),
-        );
+        // Reflections must stay above the surface and refractions below
+        if (wi.z() > 0.0) != reflect || Self::half_vector(&wo, &wi, eta).is_none() {
+            return false;
+        }
+
+        // f * cos / pdf, with most terms cancelling against the visible normal density
+        let fresnel = if reflect {
+            reflectance / reflect_probability
+        } else {
+            (Color::ones() - reflectance) / (1.0 - reflect_probability)
+        };
+        *attenuation =
+            attenuation.component_mul(&fresnel) * distribution.g(&wo, &wi) / distribution.g1(&wo);
+        *scattered = Self::continue_path(r_in, rec, onb.to_world(&wi), interface);
         true
     }
+
+    // Smooth spots of a roughness texture still scatter specularly, with a zero density
+    fn is_specular(&self) -> bool {
+        self.roughness.is_none()
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> Color {
+        let (Ok(interface), Some(distribution)) =
+            (self.interface(r_in, rec), self.distribution(rec))
+        else {
+            return Color::zeros();
+        };
+
+        let onb = Onb::from_w(&rec.normal);
+        let wo = onb.to_local(&-r_in.direction());
+        let wi = onb.to_local(direction);
+        self.rough_eval(rec, &interface, &distribution, &wo, &wi).0
+    }
+
+    fn pdf(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> f64 {
+        let (Ok(interface), Some(distribution)) =
+            (self.interface(r_in, rec), self.distribution(rec))
+        else {
+            return 0.0;
+        };
+
+        let onb = Onb::from_w(&rec.normal);
+        let wo = onb.to_local(&-r_in.direction());
+        let wi = onb.to_local(direction);
+        self.rough_eval(rec, &interface, &distribution, &wo, &wi).1
+    }
+
+    fn media_towards(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        direction: &Vec3,
+    ) -> Option<Vec<NestedMedium>> {
+        let interface = self.interface(r_in, rec).ok()?;
+        Some(Self::media_on_side(r_in, rec, direction, interface))
+    }
 }
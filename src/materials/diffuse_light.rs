@@ -0,0 +1,64 @@
+use std::rc::Rc;
+
+use crate::geometry::HitRecord;
+use crate::lights::IesProfile;
+use crate::materials::Material;
+use crate::optics::Ray;
+use crate::textures::{Solid, Texture};
+use crate::utils::{Color, ColorExt, Onb, Sampler, Vec3};
+
+#[derive(Debug)]
+pub struct DiffuseLight {
+    emit: Rc<dyn Texture>,
+    profile: Option<Rc<IesProfile>>,
+}
+
+impl DiffuseLight {
+    pub fn from_color(emit: Color) -> Self {
+        Self::from_texture(Rc::new(Solid::new(emit)))
+    }
+
+    pub fn from_texture(emit: Rc<dyn Texture>) -> Self {
+        Self {
+            emit,
+            profile: None,
+        }
+    }
+
+    // Emitted luminance equals `intensity`
+    pub fn from_blackbody(temperature: f64, intensity: f64) -> Self {
+        Self::from_color(Color::blackbody(temperature) * intensity)
+    }
+
+    // The photometric axis is aligned with the surface normal
+    pub fn from_ies(emit: Color, profile: Rc<IesProfile>) -> Self {
+        Self {
+            emit: Rc::new(Solid::new(emit)),
+            profile: Some(profile),
+        }
+    }
+}
+
+impl Material for DiffuseLight {
+    fn scatter(&self, _: &Ray, _: &HitRecord, _: &mut Sampler, _: &mut Vec3, _: &mut Ray) -> bool {
+        false
+    }
+
+    fn is_emissive(&self) -> bool {
+        true
+    }
+
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        if !rec.front_face {
+            return Color::zeros();
+        }
+
+        let emit = self.emit.sample(rec.u, rec.v, &rec.p);
+        match &self.profile {
+            Some(profile) => {
+                emit * profile.direction_factor(&Onb::from_w(&rec.normal), &-r_in.direction())
+            }
+            None => emit,
+        }
+    }
+}
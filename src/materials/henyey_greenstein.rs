@@ -0,0 +1,84 @@
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use rand::Rng;
+
+use crate::geometry::HitRecord;
+use crate::materials::Material;
+use crate::optics::Ray;
+use crate::textures::{Solid, Texture};
+use crate::utils::{Color, Onb, Sampler, Vec3};
+
+// Anisotropic phase function, `g` > 0 scatters forward and `g` < 0 backward
+#[derive(Debug)]
+pub struct HenyeyGreenstein {
+    albedo: Rc<dyn Texture>,
+    g: f64,
+}
+
+impl HenyeyGreenstein {
+    pub fn from_albedo(albedo: Color, g: f64) -> Self {
+        Self::from_texture(Rc::new(Solid::new(albedo)), g)
+    }
+
+    pub fn from_texture(texture: Rc<dyn Texture>, g: f64) -> Self {
+        Self {
+            albedo: texture,
+            g: g.clamp(-0.99, 0.99),
+        }
+    }
+}
+
+impl HenyeyGreenstein {
+    // Density over the sphere, `cos_theta` between the incoming and the scattered direction
+    fn phase(&self, cos_theta: f64) -> f64 {
+        let denom = 1.0 + self.g * self.g - 2.0 * self.g * cos_theta;
+        (1.0 - self.g * self.g) / (4.0 * PI * denom * denom.sqrt())
+    }
+}
+
+impl Material for HenyeyGreenstein {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        rng: &mut Sampler,
+        attenuation: &mut Vec3,
+        scattered: &mut Ray,
+    ) -> bool {
+        let g = self.g;
+        let u: f64 = rng.gen();
+        let cos_theta = if g.abs() < 1e-3 {
+            1.0 - 2.0 * u
+        } else {
+            let s = (1.0 - g * g) / (1.0 - g + 2.0 * g * u);
+            ((1.0 + g * g - s * s) / (2.0 * g)).clamp(-1.0, 1.0)
+        };
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * PI * rng.gen::<f64>();
+
+        let frame = Onb::from_w(&r_in.direction());
+        let direction = frame.to_world(&Vec3::new(
+            sin_theta * phi.cos(),
+            sin_theta * phi.sin(),
+            cos_theta,
+        ));
+
+        *scattered = r_in.scattered(rec.p, direction);
+        *attenuation = self.albedo.sample(rec.u, rec.v, &rec.p);
+        true
+    }
+
+    fn is_specular(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> Color {
+        let cos_theta = r_in.direction().dot(&direction.normalize());
+        self.albedo.sample(rec.u, rec.v, &rec.p) * self.phase(cos_theta)
+    }
+
+    fn pdf(&self, r_in: &Ray, _: &HitRecord, direction: &Vec3) -> f64 {
+        self.phase(r_in.direction().dot(&direction.normalize()))
+    }
+}
@@ -0,0 +1,51 @@
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::geometry::HitRecord;
+use crate::materials::Material;
+use crate::optics::Ray;
+use crate::textures::{Solid, Texture};
+use crate::utils::{Color, Sampler, Vec3, Vec3Ext};
+
+// Phase function scattering uniformly in all directions
+#[derive(Debug)]
+pub struct Isotropic {
+    albedo: Rc<dyn Texture>,
+}
+
+impl Isotropic {
+    pub fn from_albedo(albedo: Color) -> Self {
+        Self::from_texture(Rc::new(Solid::new(albedo)))
+    }
+
+    pub fn from_texture(texture: Rc<dyn Texture>) -> Self {
+        Self { albedo: texture }
+    }
+}
+
+impl Material for Isotropic {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        rng: &mut Sampler,
+        attenuation: &mut Vec3,
+        scattered: &mut Ray,
+    ) -> bool {
+        *scattered = r_in.scattered(rec.p, Vec3::random_unit_sphere_vector(rng));
+        *attenuation = self.albedo.sample(rec.u, rec.v, &rec.p);
+        true
+    }
+
+    fn is_specular(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, _: &Ray, rec: &HitRecord, _: &Vec3) -> Color {
+        self.albedo.sample(rec.u, rec.v, &rec.p) / (4.0 * PI)
+    }
+
+    fn pdf(&self, _: &Ray, _: &HitRecord, _: &Vec3) -> f64 {
+        1.0 / (4.0 * PI)
+    }
+}
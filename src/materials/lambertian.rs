@@ -1,11 +1,11 @@
-use rand::prelude::ThreadRng;
+use std::f64::consts::PI;
 use std::rc::Rc;
 
 use crate::geometry::HitRecord;
 use crate::materials::Material;
 use crate::optics::Ray;
 use crate::textures::{Solid, Texture};
-use crate::utils::{Vec3, Vec3Ext};
+use crate::utils::{Color, Sampler, Vec3, Vec3Ext};
 
 #[derive(Debug)]
 pub struct Lambertian {
@@ -32,7 +32,7 @@ impl Material for Lambertian {
         &self,
         r_in: &Ray,
         rec: &HitRecord,
-        rng: &mut ThreadRng,
+        rng: &mut Sampler,
         attenuation: &mut Vec3,
         scattered: &mut Ray,
     ) -> bool {
@@ -42,8 +42,21 @@ impl Material for Lambertian {
             scatter_direction = rec.normal;
         }
 
-        *scattered = Ray::new(rec.p, scatter_direction, r_in.time());
+        *scattered = r_in.scattered(rec.p, scatter_direction);
         *attenuation = self.albedo.sample(rec.u, rec.v, &rec.p);
         true
     }
+
+    fn is_specular(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, _: &Ray, rec: &HitRecord, direction: &Vec3) -> Color {
+        let cos_theta = rec.normal.dot(direction).max(0.0);
+        self.albedo.sample(rec.u, rec.v, &rec.p) * cos_theta / PI
+    }
+
+    fn pdf(&self, _: &Ray, rec: &HitRecord, direction: &Vec3) -> f64 {
+        rec.normal.dot(direction).max(0.0) / PI
+    }
 }
@@ -1,17 +1,54 @@
-use rand::prelude::ThreadRng;
 use std::fmt::Debug;
 
 use crate::geometry::HitRecord;
-use crate::optics::Ray;
-use crate::utils::Vec3;
+use crate::optics::{NestedMedium, Ray};
+use crate::utils::{Color, Sampler, Vec3};
 
 pub trait Material: Debug {
     fn scatter(
         &self,
         r_in: &Ray,
         rec: &HitRecord,
-        rng: &mut ThreadRng,
+        rng: &mut Sampler,
         attenuation: &mut Vec3,
         scattered: &mut Ray,
     ) -> bool;
+
+    fn is_emissive(&self) -> bool {
+        false
+    }
+
+    // Specular materials cannot be evaluated towards sampled lights
+    fn is_specular(&self) -> bool {
+        true
+    }
+
+    fn emitted(&self, _r_in: &Ray, _rec: &HitRecord) -> Color {
+        Color::zeros()
+    }
+
+    // BRDF times cosine towards `direction`, zero for purely specular materials
+    fn eval(&self, _r_in: &Ray, _rec: &HitRecord, _direction: &Vec3) -> Color {
+        Color::zeros()
+    }
+
+    // Solid angle density with which `scatter` picks `direction`
+    fn pdf(&self, _r_in: &Ray, _rec: &HitRecord, _direction: &Vec3) -> f64 {
+        0.0
+    }
+
+    // Chance that a hit at `p` is kept, rays pass through the surface elsewhere
+    fn opacity(&self, _u: f64, _v: f64, _p: &Vec3) -> f64 {
+        1.0
+    }
+
+    // Media a ray leaving towards `direction` travels through, the same as `r_in` if none
+    fn media_towards(
+        &self,
+        _r_in: &Ray,
+        _rec: &HitRecord,
+        _direction: &Vec3,
+    ) -> Option<Vec<NestedMedium>> {
+        None
+    }
 }
@@ -1,14 +1,15 @@
-use rand::prelude::ThreadRng;
+use nalgebra::Complex;
 
 use crate::geometry::HitRecord;
-use crate::materials::Material;
+use crate::materials::{channel_at, Material, ThinFilm};
 use crate::optics::Ray;
-use crate::utils::{Vec3, Vec3Ext};
+use crate::utils::{Color, Sampler, Vec3, Vec3Ext};
 
 #[derive(Debug)]
 pub struct Metal {
     albedo: Vec3,
     fuzz: f64,
+    thin_film: Option<ThinFilm>,
 }
 
 impl Metal {
@@ -16,8 +17,32 @@ impl Metal {
         Self {
             albedo,
             fuzz: if fuzz < 1.0 { fuzz } else { 1.0 },
+            thin_film: None,
         }
     }
+
+    // Film over a substrate whose bare reflectance is the albedo
+    pub fn with_thin_film(mut self, thin_film: ThinFilm) -> Self {
+        self.thin_film = Some(thin_film);
+        self
+    }
+}
+
+impl Metal {
+    // Complex index reflecting the albedo at normal incidence, with the albedo as edge tint too
+    // (Gulbrandsen 2014)
+    fn substrate(&self) -> (Color, Color) {
+        let r = self.albedo.map(|c| c.clamp(0.0, 0.99));
+        let eta =
+            r.map(|r| (1.0 - r) / (1.0 + r) * r + (1.0 + r.sqrt()) / (1.0 - r.sqrt()) * (1.0 - r));
+        let k = Color::from_fn(|i, _| {
+            let (r, n) = (r[i], eta[i]);
+            ((r * (n + 1.0).powi(2) - (n - 1.0).powi(2)) / (1.0 - r))
+                .max(0.0)
+                .sqrt()
+        });
+        (eta, k)
+    }
 }
 
 impl Default for Metal {
@@ -31,17 +56,25 @@ impl Material for Metal {
         &self,
         r_in: &Ray,
         rec: &HitRecord,
-        rng: &mut ThreadRng,
+        rng: &mut Sampler,
         attenuation: &mut Vec3,
         scattered: &mut Ray,
     ) -> bool {
         let reflected = r_in.direction().reflect(&rec.normal);
-        *scattered = Ray::new(
+        *scattered = r_in.scattered(
             rec.p,
             reflected + self.fuzz * Vec3::random_unit_sphere_vector(rng),
-            r_in.time(),
         );
-        *attenuation = self.albedo;
+        *attenuation = match &self.thin_film {
+            Some(thin_film) => {
+                let (eta, k) = self.substrate();
+                let cos_theta = (-r_in.direction()).dot(&rec.normal);
+                thin_film.reflectance_rgb(rec, cos_theta, 1.0, |lambda| {
+                    Complex::new(channel_at(&eta, lambda), channel_at(&k, lambda))
+                })
+            }
+            None => self.albedo,
+        };
 
         scattered.direction().dot(&rec.normal) > 0.0
     }
@@ -0,0 +1,114 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+
+use crate::utils::{Sampler, Vec3, Vec3Ext};
+
+// Below this roughness the distribution is treated as a perfect mirror
+const SMOOTH_ALPHA: f64 = 1e-3;
+
+// GGX / Trowbridge-Reitz distribution of microfacet normals, in the local frame of the surface
+// with the normal along z
+#[derive(Debug, Clone, Copy)]
+pub struct TrowbridgeReitz {
+    alpha_x: f64,
+    alpha_y: f64,
+}
+
+impl TrowbridgeReitz {
+    pub fn new(alpha_x: f64, alpha_y: f64) -> Self {
+        Self {
+            alpha_x: alpha_x.max(1e-4),
+            alpha_y: alpha_y.max(1e-4),
+        }
+    }
+
+    // Perceptually linear roughness in [0, 1], alpha is its square
+    pub fn from_roughness(roughness: f64) -> Self {
+        let alpha = roughness.clamp(0.0, 1.0).powi(2);
+        Self::new(alpha, alpha)
+    }
+
+    // Stretched along the tangent for `anisotropy` in [0, 1], keeping the average roughness
+    pub fn with_anisotropy(self, anisotropy: f64) -> Self {
+        let alpha = (self.alpha_x * self.alpha_y).sqrt();
+        let aspect = (1.0 - 0.9 * anisotropy.clamp(0.0, 1.0)).sqrt();
+        Self::new(alpha / aspect, alpha * aspect)
+    }
+
+    pub fn effectively_smooth(&self) -> bool {
+        self.alpha_x.max(self.alpha_y) < SMOOTH_ALPHA
+    }
+}
+
+impl TrowbridgeReitz {
+    pub fn d(&self, wm: &Vec3) -> f64 {
+        let cos2_theta = wm.z() * wm.z();
+        if cos2_theta <= 0.0 {
+            return 0.0;
+        }
+        let e = ((wm.x() / self.alpha_x).powi(2) + (wm.y() / self.alpha_y).powi(2)) / cos2_theta;
+        1.0 / (PI * self.alpha_x * self.alpha_y * cos2_theta * cos2_theta * (1.0 + e).powi(2))
+    }
+
+    // Smith's auxiliary function, the ratio of hidden to visible microfacet area towards `w`
+    pub fn lambda(&self, w: &Vec3) -> f64 {
+        let cos2_theta = w.z() * w.z();
+        if cos2_theta <= 0.0 {
+            return f64::INFINITY;
+        }
+        let alpha2_tan2_theta =
+            ((w.x() * self.alpha_x).powi(2) + (w.y() * self.alpha_y).powi(2)) / cos2_theta;
+        ((1.0 + alpha2_tan2_theta).sqrt() - 1.0) / 2.0
+    }
+
+    pub fn g1(&self, w: &Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(w))
+    }
+
+    // Height correlated masking and shadowing
+    pub fn g(&self, wo: &Vec3, wi: &Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    // Density of the microfacet normals visible from `w`
+    pub fn visible_d(&self, w: &Vec3, wm: &Vec3) -> f64 {
+        if w.z() == 0.0 {
+            return 0.0;
+        }
+        self.g1(w) / w.z().abs() * self.d(wm) * w.dot(wm).abs()
+    }
+
+    // Samples a visible microfacet normal (Heitz 2018), its density is `visible_d`
+    pub fn sample_wm(&self, w: &Vec3, rng: &mut Sampler) -> Vec3 {
+        // Stretch to the hemisphere configuration
+        let mut wh = Vec3::new(self.alpha_x * w.x(), self.alpha_y * w.y(), w.z()).normalize();
+        if wh.z() < 0.0 {
+            wh = -wh;
+        }
+        let t1 = if wh.z() < 0.99999 {
+            Vec3::new(0.0, 0.0, 1.0).cross(&wh).normalize()
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let t2 = wh.cross(&t1);
+
+        // Uniform disk, warped to the projection of the visible hemisphere
+        let r = rng.gen::<f64>().sqrt();
+        let phi = 2.0 * PI * rng.gen::<f64>();
+        let (px, py) = (r * phi.cos(), r * phi.sin());
+        let h = (1.0 - px * px).sqrt();
+        let s = (1.0 + wh.z()) / 2.0;
+        let py = (1.0 - s) * h + s * py;
+        let pz = (1.0 - px * px - py * py).max(0.0).sqrt();
+        let nh = px * t1 + py * t2 + pz * wh;
+
+        // Back to the ellipsoid configuration
+        Vec3::new(
+            self.alpha_x * nh.x(),
+            self.alpha_y * nh.y(),
+            nh.z().max(1e-6),
+        )
+        .normalize()
+    }
+}
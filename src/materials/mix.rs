@@ -0,0 +1,113 @@
+use rand::Rng;
+use std::rc::Rc;
+
+use crate::geometry::{HitRecord, RaySampler};
+use crate::materials::Material;
+use crate::optics::{NestedMedium, Ray};
+use crate::textures::Texture;
+use crate::utils::{Color, ColorExt, Sampler, Vec3};
+
+// Blend of two materials by the gray level of a mask, zero for the first and one for the second.
+// Each scattering event picks one of them, so binary masks choose and gray ones blend
+// stochastically.
+#[derive(Debug)]
+pub struct Mix {
+    first: Rc<dyn Material>,
+    second: Rc<dyn Material>,
+    mask: Rc<dyn Texture>,
+}
+
+impl Mix {
+    pub fn new(first: Rc<dyn Material>, second: Rc<dyn Material>, mask: Rc<dyn Texture>) -> Self {
+        Self {
+            first,
+            second,
+            mask,
+        }
+    }
+
+    fn weight(&self, rec: &HitRecord) -> f64 {
+        self.mask
+            .sample(rec.u, rec.v, &rec.p)
+            .luminance()
+            .clamp(0.0, 1.0)
+    }
+
+    // Material a scattering event picks, drawn from the incoming ray so that light sampled from
+    // the same hit goes through the media of the same one
+    fn choose(&self, r_in: &Ray, rec: &HitRecord) -> &Rc<dyn Material> {
+        let weight = self.weight(rec);
+        let mut rng = RaySampler::new(r_in, weight.to_bits());
+        if rng.gen::<f64>() < weight {
+            &self.second
+        } else {
+            &self.first
+        }
+    }
+}
+
+impl Material for Mix {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        rng: &mut Sampler,
+        attenuation: &mut Vec3,
+        scattered: &mut Ray,
+    ) -> bool {
+        let weight = self.weight(rec);
+        let chosen = self.choose(r_in, rec);
+        if !chosen.scatter(r_in, rec, rng, attenuation, scattered) {
+            return false;
+        }
+
+        // Where both have a density, weigh by the blended one like other directions are
+        if weight > 0.0 && weight < 1.0 && !self.is_specular() {
+            let pdf = self.pdf(r_in, rec, &scattered.direction());
+            if pdf > 0.0 {
+                *attenuation = self.eval(r_in, rec, &scattered.direction()) / pdf;
+            }
+        }
+        true
+    }
+
+    fn is_emissive(&self) -> bool {
+        self.first.is_emissive() || self.second.is_emissive()
+    }
+
+    // Light sampling only where neither of them is specular
+    fn is_specular(&self) -> bool {
+        self.first.is_specular() || self.second.is_specular()
+    }
+
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        let weight = self.weight(rec);
+        self.first.emitted(r_in, rec) * (1.0 - weight) + self.second.emitted(r_in, rec) * weight
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> Color {
+        let weight = self.weight(rec);
+        self.first.eval(r_in, rec, direction) * (1.0 - weight)
+            + self.second.eval(r_in, rec, direction) * weight
+    }
+
+    fn pdf(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> f64 {
+        let weight = self.weight(rec);
+        self.first.pdf(r_in, rec, direction) * (1.0 - weight)
+            + self.second.pdf(r_in, rec, direction) * weight
+    }
+
+    fn opacity(&self, u: f64, v: f64, p: &Vec3) -> f64 {
+        let weight = self.mask.sample(u, v, p).luminance().clamp(0.0, 1.0);
+        self.first.opacity(u, v, p) * (1.0 - weight) + self.second.opacity(u, v, p) * weight
+    }
+
+    fn media_towards(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        direction: &Vec3,
+    ) -> Option<Vec<NestedMedium>> {
+        self.choose(r_in, rec).media_towards(r_in, rec, direction)
+    }
+}
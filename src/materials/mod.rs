@@ -1,12 +1,38 @@
+pub mod bump;
+pub mod coated;
+pub mod conductor;
+pub mod cutout;
 pub mod dielectric;
+pub mod diffuse_light;
+pub mod fresnel;
+pub mod henyey_greenstein;
+pub mod isotropic;
 pub mod lambertian;
 pub mod material;
 pub mod metal;
+pub mod microfacet;
+pub mod mix;
 pub mod normal;
+pub mod oren_nayar;
+pub mod principled;
+pub mod thin_film;
 
 // Export
+pub use bump::*;
+pub use coated::*;
+pub use conductor::*;
+pub use cutout::*;
 pub use dielectric::*;
+pub use diffuse_light::*;
+pub use fresnel::*;
+pub use henyey_greenstein::*;
+pub use isotropic::*;
 pub use lambertian::*;
 pub use material::*;
 pub use metal::*;
+pub use microfacet::*;
+pub use mix::*;
 pub use normal::*;
+pub use oren_nayar::*;
+pub use principled::*;
+pub use thin_film::*;
@@ -1,9 +1,7 @@
-use rand::prelude::ThreadRng;
-
 use crate::geometry::HitRecord;
 use crate::materials::Material;
 use crate::optics::Ray;
-use crate::utils::{Vec3, Vec3Ext};
+use crate::utils::{Sampler, Vec3, Vec3Ext};
 
 #[derive(Debug)]
 pub struct Normal {}
@@ -25,11 +23,11 @@ impl Material for Normal {
         &self,
         r_in: &Ray,
         rec: &HitRecord,
-        _: &mut ThreadRng,
+        _: &mut Sampler,
         attenuation: &mut Vec3,
         scattered: &mut Ray,
     ) -> bool {
-        *scattered = Ray::new(rec.p, -r_in.direction(), r_in.time());
+        *scattered = r_in.scattered(rec.p, -r_in.direction());
         *attenuation = (0.5 * (rec.normal + Vec3::ones())).squared();
         true
     }
@@ -0,0 +1,135 @@
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::geometry::HitRecord;
+use crate::materials::Material;
+use crate::optics::Ray;
+use crate::textures::{Solid, Texture};
+use crate::utils::{Color, ColorExt, Sampler, Vec3, Vec3Ext};
+
+// Rough diffuse surface made of V-shaped Lambertian facets, brighter towards the light and flatter
+// than Lambertian at grazing angles, like clay or concrete. Uses the improved model of Fujii, with
+// the energy lost to the missing interreflections between facets added back as in the
+// energy-preserving Oren-Nayar of OpenPBR (Portsmouth et al. 2024).
+#[derive(Debug)]
+pub struct OrenNayar {
+    albedo: Rc<dyn Texture>,
+    roughness: Rc<dyn Texture>, // Gray level, Lambertian at zero
+    multiscatter: bool,
+}
+
+// Coefficients of the directional albedo
+const FON_A: f64 = 0.5 - 2.0 / (3.0 * PI);
+const FON_AVERAGE: f64 = 2.0 / 3.0 - 28.0 / (15.0 * PI);
+
+impl OrenNayar {
+    pub fn from_albedo(albedo: Color, roughness: f64) -> Self {
+        Self::from_texture(
+            Rc::new(Solid::new(albedo)),
+            Rc::new(Solid::new(Color::constant(roughness))),
+        )
+    }
+
+    pub fn from_texture(albedo: Rc<dyn Texture>, roughness: Rc<dyn Texture>) -> Self {
+        Self {
+            albedo,
+            roughness,
+            multiscatter: true,
+        }
+    }
+
+    // Without it, rough surfaces get darker than their albedo
+    pub fn with_multiscatter(mut self, multiscatter: bool) -> Self {
+        self.multiscatter = multiscatter;
+        self
+    }
+}
+
+impl Default for OrenNayar {
+    fn default() -> Self {
+        Self::from_albedo(Color::new(0.5, 0.5, 0.5), 0.5)
+    }
+}
+
+impl OrenNayar {
+    // Fraction of the light from `cos_theta` that a white surface reflects in a single scattering
+    fn directional_albedo(cos_theta: f64, roughness: f64) -> f64 {
+        let mu = cos_theta.clamp(1e-4, 1.0);
+        let a = 1.0 / (1.0 + FON_A * roughness);
+        let b = roughness * a;
+        let sin = (1.0 - mu * mu).sqrt();
+        let g = sin * (mu.acos() - sin * mu) + 2.0 / 3.0 * ((sin / mu) * (1.0 - sin.powi(3)) - sin);
+        a + b / PI * g
+    }
+
+    // BRDF times cosine, with the normal facing `wo`
+    fn brdf_cos(&self, rec: &HitRecord, wo: &Vec3, wi: &Vec3) -> Color {
+        let cos_o = rec.normal.dot(wo);
+        let cos_i = rec.normal.dot(wi);
+        if cos_o <= 0.0 || cos_i <= 0.0 {
+            return Color::zeros();
+        }
+
+        let albedo = self.albedo.sample(rec.u, rec.v, &rec.p);
+        let roughness = self
+            .roughness
+            .sample(rec.u, rec.v, &rec.p)
+            .luminance()
+            .clamp(0.0, 1.0);
+
+        let s = wi.dot(wo) - cos_i * cos_o;
+        let s_over_t = if s > 0.0 { s / cos_i.max(cos_o) } else { s };
+        let a = 1.0 / (1.0 + FON_A * roughness);
+        let mut brdf = albedo * a * (1.0 + roughness * s_over_t) / PI;
+
+        if self.multiscatter {
+            let average = a * (1.0 + FON_AVERAGE * roughness);
+            let albedo_ms = albedo
+                .component_mul(&albedo)
+                .component_div(&(Color::ones() - albedo * (1.0 - average)))
+                * average;
+            let lost = |cos: f64| (1.0 - Self::directional_albedo(cos, roughness)).max(1e-7);
+            brdf += albedo_ms * lost(cos_o) * lost(cos_i) / ((1.0 - average).max(1e-7) * PI);
+        }
+
+        brdf * cos_i
+    }
+}
+
+impl Material for OrenNayar {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        rng: &mut Sampler,
+        attenuation: &mut Vec3,
+        scattered: &mut Ray,
+    ) -> bool {
+        let mut scatter_direction = rec.normal + Vec3::random_unit_sphere_vector(rng);
+
+        if scatter_direction.near_zero() {
+            scatter_direction = rec.normal;
+        }
+
+        *scattered = r_in.scattered(rec.p, scatter_direction);
+        let direction = scattered.direction();
+        let pdf = self.pdf(r_in, rec, &direction);
+        if pdf <= 0.0 {
+            return false;
+        }
+        *attenuation = self.eval(r_in, rec, &direction) / pdf;
+        true
+    }
+
+    fn is_specular(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> Color {
+        self.brdf_cos(rec, &-r_in.direction(), direction)
+    }
+
+    fn pdf(&self, _: &Ray, rec: &HitRecord, direction: &Vec3) -> f64 {
+        rec.normal.dot(direction).max(0.0) / PI
+    }
+}
@@ -0,0 +1,383 @@
+use rand::Rng;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::geometry::HitRecord;
+use crate::materials::{Dielectric, Material, TrowbridgeReitz};
+use crate::optics::Ray;
+use crate::textures::{Channel, Solid, Texture, UVImage};
+use crate::utils::{Color, ColorExt, Onb, Sampler, Vec3, Vec3Ext};
+
+// Inputs of the principled material, scalars are read from the gray level of their texture
+#[derive(Debug, Clone)]
+pub struct PrincipledConfig {
+    pub base_color: Rc<dyn Texture>,
+    pub metallic: Rc<dyn Texture>,
+    pub roughness: Rc<dyn Texture>,
+    pub specular: Rc<dyn Texture>, // 0.5 reflects 4% at normal incidence
+    pub sheen: Rc<dyn Texture>,    // Color of the grazing rim of cloth-like surfaces
+    pub clearcoat: Rc<dyn Texture>,
+    pub clearcoat_roughness: Rc<dyn Texture>,
+    pub transmission: Rc<dyn Texture>,
+    pub ior: f64,
+    pub emission: Option<Rc<dyn Texture>>,
+    pub opacity: Rc<dyn Texture>, // Cut out where transparent, unlike transmission it does not refract
+}
+
+fn constant(value: f64) -> Rc<dyn Texture> {
+    Rc::new(Solid::new(Color::constant(value)))
+}
+
+impl Default for PrincipledConfig {
+    fn default() -> Self {
+        Self {
+            base_color: constant(0.8),
+            metallic: constant(0.0),
+            roughness: constant(0.5),
+            specular: constant(0.5),
+            sheen: constant(0.0),
+            clearcoat: constant(0.0),
+            clearcoat_roughness: constant(0.03),
+            transmission: constant(0.0),
+            ior: 1.5,
+            emission: None,
+            opacity: constant(1.0),
+        }
+    }
+}
+
+impl PrincipledConfig {
+    // glTF metallic-roughness model, metalness is in the blue and roughness in the green channel
+    pub fn from_gltf(
+        base_color: Rc<dyn Texture>,
+        metallic_roughness: Rc<dyn Texture>,
+        emission: Option<Rc<dyn Texture>>,
+    ) -> Self {
+        Self {
+            base_color,
+            metallic: Rc::new(Channel::new(metallic_roughness.clone(), 2)),
+            roughness: Rc::new(Channel::new(metallic_roughness, 1)),
+            emission,
+            ..Self::default()
+        }
+    }
+
+    // Materials of an MTL file by name, with the PBR extension (Pr, Pm, Ps, Pc, Pcr and their maps).
+    // Dissolve (d, Tr) is opacity, the transmission filter (Tf) and Ni make it glass.
+    pub fn load_mtl(path: &str) -> HashMap<String, Self> {
+        let path = Path::new(path);
+        let mtl_file = std::fs::read_to_string(path).unwrap();
+        let directory = path.parent().unwrap_or(Path::new(""));
+
+        let mut materials = HashMap::new();
+        let mut name = None;
+        let mut config = Self::default();
+
+        for line in mtl_file.lines() {
+            let mut parts = line.split_whitespace();
+            let Some(key) = parts.next() else {
+                continue;
+            };
+            let values: Vec<&str> = parts.collect();
+            let scalar = || constant(values[0].parse().unwrap());
+            let color = || -> Rc<dyn Texture> {
+                let c: Vec<f64> = values.iter().map(|v| v.parse().unwrap()).collect();
+                match c[..] {
+                    [r, g, b, ..] => Rc::new(Solid::new(Color::new(r, g, b))),
+                    _ => constant(c[0]),
+                }
+            };
+            let map = || -> Rc<dyn Texture> {
+                let file = directory.join(values.last().unwrap());
+                Rc::new(UVImage::new(file.to_str().unwrap()))
+            };
+
+            match key {
+                "newmtl" => {
+                    if let Some(name) = name.replace(values[0].to_string()) {
+                        materials.insert(name, std::mem::take(&mut config));
+                    }
+                }
+                "Kd" => config.base_color = color(),
+                "map_Kd" => config.base_color = map(),
+                "Ke" => config.emission = Some(color()),
+                "map_Ke" => config.emission = Some(map()),
+                "Ni" => config.ior = values[0].parse().unwrap(),
+                "Tf" => config.transmission = color(),
+                "d" => config.opacity = scalar(),
+                "map_d" => config.opacity = map(),
+                "Tr" => config.opacity = constant(1.0 - values[0].parse::<f64>().unwrap()),
+                "Pr" => config.roughness = scalar(),
+                "map_Pr" => config.roughness = map(),
+                "Pm" => config.metallic = scalar(),
+                "map_Pm" => config.metallic = map(),
+                "Ps" => config.sheen = color(),
+                "map_Ps" => config.sheen = map(),
+                "Pc" => config.clearcoat = scalar(),
+                "Pcr" => config.clearcoat_roughness = scalar(),
+                _ => {}
+            }
+        }
+        if let Some(name) = name {
+            materials.insert(name, config);
+        }
+
+        materials
+    }
+}
+
+// Disney-style uber material: diffuse with sheen, GGX specular blending from dielectric to metal,
+// a clear coat on top, and rough glass for transmission. Lobes are picked with fixed heuristic
+// weights, the estimate uses the mixture of all their densities.
+#[derive(Debug)]
+pub struct Principled {
+    config: PrincipledConfig,
+    glass: Dielectric,
+}
+
+// Inputs at a hit point, turned into lobe weights
+struct Lobes {
+    base_color: Color,
+    diffuse: f64, // Opaque dielectric part
+    sheen: Color,
+    glass: f64,
+    specular_f0: Color,
+    specular: TrowbridgeReitz,
+    clearcoat: f64,
+    coat: TrowbridgeReitz,
+
+    // Selection probabilities
+    pick_diffuse: f64,
+    pick_specular: f64,
+    pick_coat: f64,
+    pick_glass: f64,
+}
+
+impl Principled {
+    pub fn new(config: PrincipledConfig) -> Self {
+        let glass = Dielectric::new(config.ior).with_roughness(config.roughness.clone());
+        Self { config, glass }
+    }
+}
+
+impl Default for Principled {
+    fn default() -> Self {
+        Self::new(PrincipledConfig::default())
+    }
+}
+
+fn schlick(f0: &Color, cos_theta: f64) -> Color {
+    f0 + (Color::ones() - f0) * (1.0 - cos_theta.clamp(0.0, 1.0)).powi(5)
+}
+
+impl Principled {
+    fn lobes(&self, rec: &HitRecord) -> Lobes {
+        let sample = |texture: &Rc<dyn Texture>| texture.sample(rec.u, rec.v, &rec.p);
+        let scalar = |texture: &Rc<dyn Texture>| sample(texture).luminance().clamp(0.0, 1.0);
+
+        let base_color = sample(&self.config.base_color);
+        let metallic = scalar(&self.config.metallic);
+        let transmission = scalar(&self.config.transmission);
+        let clearcoat = scalar(&self.config.clearcoat);
+
+        let diffuse = (1.0 - metallic) * (1.0 - transmission);
+        let glass = (1.0 - metallic) * transmission;
+        let dielectric_f0 = Color::constant(0.08 * scalar(&self.config.specular));
+
+        let weights = [
+            diffuse,
+            (1.0 - glass) * (0.5 + 0.5 * metallic),
+            0.5 * clearcoat,
+            glass,
+        ];
+        let total: f64 = weights.iter().sum();
+
+        Lobes {
+            base_color,
+            diffuse,
+            sheen: sample(&self.config.sheen) * diffuse,
+            glass,
+            specular_f0: dielectric_f0.lerp(&base_color, metallic),
+            specular: TrowbridgeReitz::from_roughness(scalar(&self.config.roughness)),
+            clearcoat,
+            coat: TrowbridgeReitz::from_roughness(scalar(&self.config.clearcoat_roughness)),
+            pick_diffuse: weights[0] / total,
+            pick_specular: weights[1] / total,
+            pick_coat: weights[2] / total,
+            pick_glass: weights[3] / total,
+        }
+    }
+
+    // Opaque lobes, BRDF times cosine in the local frame
+    fn eval_reflection(lobes: &Lobes, wo: &Vec3, wi: &Vec3) -> Color {
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return Color::zeros();
+        }
+        let wh = (wo + wi).normalize();
+
+        let diffuse = lobes.base_color * lobes.diffuse / PI;
+        let sheen = lobes.sheen * (1.0 - wi.dot(&wh)).powi(5);
+
+        let microfacet = |distribution: &TrowbridgeReitz| {
+            distribution.d(&wh) * distribution.g(wo, wi) / (4.0 * wo.z())
+        };
+        let specular = schlick(&lobes.specular_f0, wo.dot(&wh))
+            * (1.0 - lobes.glass)
+            * microfacet(&lobes.specular);
+        let coat = schlick(&Color::constant(0.04), wo.dot(&wh))
+            * lobes.clearcoat
+            * microfacet(&lobes.coat);
+
+        (diffuse + sheen) * wi.z() + specular + coat
+    }
+
+    fn pdf_reflection(lobes: &Lobes, wo: &Vec3, wi: &Vec3) -> f64 {
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return 0.0;
+        }
+        let wh = (wo + wi).normalize();
+
+        let microfacet =
+            |distribution: &TrowbridgeReitz| distribution.visible_d(wo, &wh) / (4.0 * wo.dot(&wh));
+        lobes.pick_diffuse * wi.z() / PI
+            + lobes.pick_specular * microfacet(&lobes.specular)
+            + lobes.pick_coat * microfacet(&lobes.coat)
+    }
+
+    // Transmission is tinted by the square root of the base color on each crossing
+    fn glass_tint(lobes: &Lobes, rec: &HitRecord, direction: &Vec3) -> Color {
+        if direction.dot(&rec.normal) < 0.0 {
+            lobes.base_color.map(|c| c.max(0.0).sqrt())
+        } else {
+            Color::ones()
+        }
+    }
+}
+
+impl Material for Principled {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        rng: &mut Sampler,
+        attenuation: &mut Vec3,
+        scattered: &mut Ray,
+    ) -> bool {
+        let lobes = self.lobes(rec);
+
+        // Inside a transmissive object there is only glass, still weighted by the transmission
+        if !rec.front_face && lobes.glass > 0.0 {
+            if !self.glass.scatter(r_in, rec, rng, attenuation, scattered) {
+                return false;
+            }
+            *attenuation =
+                attenuation.component_mul(&Self::glass_tint(&lobes, rec, &scattered.direction()))
+                    * lobes.glass;
+            return true;
+        }
+
+        let onb = Onb::from_w(&rec.normal);
+        let wo = onb.to_local(&-r_in.direction());
+
+        let u = rng.gen::<f64>();
+        if u < lobes.pick_glass {
+            if !self.glass.scatter(r_in, rec, rng, attenuation, scattered) {
+                return false;
+            }
+
+            // Passing through without an interface, or perfectly smooth
+            let pdf = self.pdf(r_in, rec, &scattered.direction());
+            if pdf <= 0.0 {
+                *attenuation = attenuation.component_mul(&Self::glass_tint(
+                    &lobes,
+                    rec,
+                    &scattered.direction(),
+                )) * (lobes.glass / lobes.pick_glass);
+                return true;
+            }
+            *attenuation = self.eval(r_in, rec, &scattered.direction()) / pdf;
+            return true;
+        }
+
+        let wi = if u < lobes.pick_glass + lobes.pick_diffuse {
+            let direction = rec.normal + Vec3::random_unit_sphere_vector(rng);
+            if direction.near_zero() {
+                Vec3::new(0.0, 0.0, 1.0)
+            } else {
+                onb.to_local(&direction.normalize())
+            }
+        } else {
+            let distribution = if u < 1.0 - lobes.pick_coat {
+                &lobes.specular
+            } else {
+                &lobes.coat
+            };
+            let wm = distribution.sample_wm(&wo, rng);
+            (-wo).reflect(&wm)
+        };
+
+        let direction = onb.to_world(&wi);
+        let pdf = self.pdf(r_in, rec, &direction);
+        if wi.z() <= 0.0 || pdf <= 0.0 {
+            return false;
+        }
+
+        *scattered = r_in.scattered(rec.p, direction);
+        *attenuation = self.eval(r_in, rec, &direction) / pdf;
+        true
+    }
+
+    fn is_emissive(&self) -> bool {
+        self.config.emission.is_some()
+    }
+
+    fn is_specular(&self) -> bool {
+        false
+    }
+
+    fn opacity(&self, u: f64, v: f64, p: &Vec3) -> f64 {
+        self.config
+            .opacity
+            .sample(u, v, p)
+            .luminance()
+            .clamp(0.0, 1.0)
+    }
+
+    fn emitted(&self, _: &Ray, rec: &HitRecord) -> Color {
+        match &self.config.emission {
+            Some(emission) if rec.front_face => emission.sample(rec.u, rec.v, &rec.p),
+            _ => Color::zeros(),
+        }
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> Color {
+        let lobes = self.lobes(rec);
+        let glass = self
+            .glass
+            .eval(r_in, rec, direction)
+            .component_mul(&Self::glass_tint(&lobes, rec, direction));
+        if !rec.front_face && lobes.glass > 0.0 {
+            return glass * lobes.glass;
+        }
+
+        let onb = Onb::from_w(&rec.normal);
+        let wo = onb.to_local(&-r_in.direction());
+        let wi = onb.to_local(direction);
+        Self::eval_reflection(&lobes, &wo, &wi) + glass * lobes.glass
+    }
+
+    fn pdf(&self, r_in: &Ray, rec: &HitRecord, direction: &Vec3) -> f64 {
+        let lobes = self.lobes(rec);
+        let glass = self.glass.pdf(r_in, rec, direction);
+        if !rec.front_face && lobes.glass > 0.0 {
+            return glass;
+        }
+
+        let onb = Onb::from_w(&rec.normal);
+        let wo = onb.to_local(&-r_in.direction());
+        let wi = onb.to_local(direction);
+        Self::pdf_reflection(&lobes, &wo, &wi) + glass * lobes.pick_glass
+    }
+}
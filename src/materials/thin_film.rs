@@ -0,0 +1,142 @@
+use nalgebra::Complex;
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::geometry::HitRecord;
+use crate::textures::Texture;
+use crate::utils::{cie_xyz, xyz_to_rgb, Color, ColorExt, Vec3, Vec3Ext};
+
+// Wavelengths over which the reflectance is integrated to RGB, in nm
+const FILM_LAMBDA_MIN: f64 = 380.0;
+const FILM_LAMBDA_MAX: f64 = 780.0;
+const FILM_LAMBDA_STEP: f64 = 10.0;
+
+// Wavelengths at which RGB material parameters are taken to be given, in nm
+const CHANNEL_LAMBDA: [f64; 3] = [630.0, 532.0, 465.0];
+
+// Transparent film on a surface, like soap, oil or a lens coating, whose reflections off its two
+// sides interfere depending on wavelength, thickness and angle (Airy summation). The reflectance
+// spectrum is integrated to RGB, also in spectral mode.
+#[derive(Debug, Clone)]
+pub struct ThinFilm {
+    thickness: f64, // In nanometers
+    refraction_index: f64,
+    thickness_map: Option<Rc<dyn Texture>>, // Gray level scaling the thickness
+    white_balance: Color,
+}
+
+impl ThinFilm {
+    pub fn new(thickness: f64, refraction_index: f64) -> Self {
+        let mut flat = Vec3::zeros();
+        let mut lambda = FILM_LAMBDA_MIN;
+        while lambda <= FILM_LAMBDA_MAX {
+            flat += cie_xyz(lambda);
+            lambda += FILM_LAMBDA_STEP;
+        }
+
+        Self {
+            thickness: thickness.max(0.0),
+            refraction_index,
+            thickness_map: None,
+            white_balance: xyz_to_rgb(&(flat / flat.y())).map(|c| 1.0 / c),
+        }
+    }
+
+    // Soap film, iridescent from a few hundred nanometers down to black where it is thinnest
+    pub fn soap(thickness: f64) -> Self {
+        Self::new(thickness, 1.33)
+    }
+
+    // Varying thickness, e.g. the swirls of a soap bubble
+    pub fn with_thickness_map(mut self, thickness_map: Rc<dyn Texture>) -> Self {
+        self.thickness_map = Some(thickness_map);
+        self
+    }
+}
+
+impl ThinFilm {
+    // Unpolarized reflectance at `lambda` from a medium of index `incident` onto the film over a
+    // substrate of complex index `substrate`
+    pub fn reflectance(
+        &self,
+        cos_theta_i: f64,
+        incident: f64,
+        substrate: Complex<f64>,
+        thickness: f64,
+        lambda: f64,
+    ) -> f64 {
+        let cos_theta_i = cos_theta_i.clamp(0.0, 1.0);
+        let sin2_theta_i = 1.0 - cos_theta_i * cos_theta_i;
+        let n1 = Complex::new(incident, 0.0);
+        let n2 = Complex::new(self.refraction_index, 0.0);
+        let n3 = substrate;
+
+        // Snell's law in complex form, evanescent beyond the critical angle
+        let cos_in =
+            |n: Complex<f64>| (Complex::new(1.0, 0.0) - n1 * n1 * sin2_theta_i / (n * n)).sqrt();
+        let (cos1, cos2, cos3) = (Complex::new(cos_theta_i, 0.0), cos_in(n2), cos_in(n3));
+
+        // Phase difference of a round trip through the film
+        let delta = 2.0 * PI / lambda * thickness * n2 * cos2;
+        let phase = (Complex::new(0.0, 2.0) * delta).exp();
+
+        let airy = |r12: Complex<f64>, r23: Complex<f64>| {
+            ((r12 + r23 * phase) / (Complex::new(1.0, 0.0) + r12 * r23 * phase)).norm_sqr()
+        };
+        let r_perpendicular = airy(
+            (n1 * cos1 - n2 * cos2) / (n1 * cos1 + n2 * cos2),
+            (n2 * cos2 - n3 * cos3) / (n2 * cos2 + n3 * cos3),
+        );
+        let r_parallel = airy(
+            (n2 * cos1 - n1 * cos2) / (n2 * cos1 + n1 * cos2),
+            (n3 * cos2 - n2 * cos3) / (n3 * cos2 + n2 * cos3),
+        );
+        ((r_perpendicular + r_parallel) / 2.0).clamp(0.0, 1.0)
+    }
+
+    // Reflectance in RGB, with the substrate index as a function of the wavelength
+    pub fn reflectance_rgb(
+        &self,
+        rec: &HitRecord,
+        cos_theta_i: f64,
+        incident: f64,
+        substrate: impl Fn(f64) -> Complex<f64>,
+    ) -> Color {
+        let thickness = match &self.thickness_map {
+            Some(map) => self.thickness * map.sample(rec.u, rec.v, &rec.p).luminance().max(0.0),
+            None => self.thickness,
+        };
+
+        let mut xyz = Vec3::zeros();
+        let mut flat = 0.0;
+        let mut lambda = FILM_LAMBDA_MIN;
+        while lambda <= FILM_LAMBDA_MAX {
+            let cie = cie_xyz(lambda);
+            let reflectance =
+                self.reflectance(cos_theta_i, incident, substrate(lambda), thickness, lambda);
+            xyz += cie * reflectance;
+            flat += cie.y();
+            lambda += FILM_LAMBDA_STEP;
+        }
+
+        xyz_to_rgb(&(xyz / flat))
+            .component_mul(&self.white_balance)
+            .map(|c| c.clamp(0.0, 1.0))
+    }
+}
+
+// RGB material parameter at `lambda`, interpolated between the channels and constant beyond them
+pub fn channel_at(values: &Color, lambda: f64) -> f64 {
+    let [red, green, blue] = CHANNEL_LAMBDA;
+    if lambda >= red {
+        values.r()
+    } else if lambda >= green {
+        let t = (lambda - green) / (red - green);
+        values.g() + (values.r() - values.g()) * t
+    } else if lambda >= blue {
+        let t = (lambda - blue) / (green - blue);
+        values.b() + (values.g() - values.b()) * t
+    } else {
+        values.b()
+    }
+}
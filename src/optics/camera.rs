@@ -1,14 +1,16 @@
-use image::{ImageBuffer, RgbImage};
-use rand::prelude::ThreadRng;
-use rand::{thread_rng, Rng};
+use image::{ImageBuffer, Rgb32FImage, RgbImage};
+use rand::Rng;
+use std::f64::consts::PI;
 use tqdm::tqdm;
 
-use crate::geometry::{HitRecord, Hittable};
+use crate::geometry::World;
+use crate::integrators::Integrator;
 use crate::optics::{LensConfig, Ray, RenderOutputConfig, ViewportConfig};
-use crate::utils::{Color, ColorExt, Interval, Point3, Vec3, Vec3Ext};
+use crate::utils::{Color, ColorExt, Point3, Sampler, Vec3, Vec3Ext};
 
 pub struct Camera {
-    rng: ThreadRng,
+    rng: Sampler,
+    integrator: Box<dyn Integrator>,
 
     pub aspect_ratio: f64,
     pub image_width: u32,
@@ -45,7 +47,10 @@ impl Camera {
         lens_config: LensConfig,
     ) -> Self {
         Self {
-            rng: thread_rng(),
+            rng: Sampler::default(),
+            integrator: render_output_config
+                .integrator
+                .build(render_output_config.max_depth),
             aspect_ratio: render_output_config.aspect_ratio,
             image_width: render_output_config.image_width,
             samples_per_pixel: render_output_config.samples_per_pixel,
@@ -118,61 +123,45 @@ impl Camera {
         self.depth_of_field_disk_v = depth_of_field_radius * self.v;
     }
 
-    pub fn render(&mut self, world: &impl Hittable) -> RgbImage {
+    pub fn render(&mut self, world: &World) -> RgbImage {
         self.initialize();
-        let mut output_image: RgbImage = ImageBuffer::new(self.image_width, self.image_height);
-
-        for j in tqdm(0..self.image_height) {
-            for i in 0..self.image_width {
-                let mut pixel_color: Color = Color::zeros();
-
-                // Super Sampling
-                for _ in 0..self.samples_per_pixel {
-                    let r = self.get_ray(i, j);
-                    pixel_color += self.ray_color(&r, self.max_depth, world);
-                }
-
-                pixel_color /= self.samples_per_pixel as f64;
-                output_image.put_pixel(i, j, pixel_color.to_rgb8());
-            }
-        }
+        self.integrator.prepare(world, &self.projection());
+
+        let mut film = match self
+            .integrator
+            .render(world, self.samples_per_pixel, &mut self.rng)
+        {
+            Some(film) => film,
+            None => self.render_passes(world),
+        };
 
-        output_image
+        self.integrator.finish(&mut film);
+        ImageBuffer::from_fn(self.image_width, self.image_height, |i, j| {
+            Color::from_rgb32f(film.get_pixel(i, j)).to_rgb8()
+        })
     }
 
-    fn ray_color(&mut self, r: &Ray, depth: u32, world: &impl Hittable) -> Color {
-        if depth == 0 {
-            return Color::zeros();
-        }
+    fn render_passes(&mut self, world: &World) -> Rgb32FImage {
+        let mut pixel_colors =
+            vec![Color::zeros(); (self.image_width * self.image_height) as usize];
 
-        let mut rec = HitRecord::default();
-        if world.hit(r, Interval::right_open(0.001), &mut rec) {
-            let mut scattered = Ray::new(Point3::zeros(), Vec3::zeros(), 0.0);
-            let mut attenuation = Color::zeros();
-
-            // let max_intersection_checks = 100.0;
-            // rec.mat = Rc::new(Lambertian::from_albedo(Color::new(
-            //     (rec.debug.intersection_checks as f64 / max_intersection_checks)
-            //         .min(max_intersection_checks),
-            //     0.0,
-            //     0.0,
-            // )));
-
-            // println!("{} intersection checks", rec.debug.intersection_checks);
-
-            if rec
-                .mat
-                .scatter(r, &rec, &mut self.rng, &mut attenuation, &mut scattered)
-            {
-                return attenuation.component_mul(&self.ray_color(&scattered, depth - 1, world));
-            }
+        // Super Sampling, one sample per pixel in each pass
+        for pass in tqdm(0..self.samples_per_pixel) {
+            self.integrator.start_pass(world, pass, &mut self.rng);
 
-            return Color::zeros();
+            for j in 0..self.image_height {
+                for i in 0..self.image_width {
+                    let r = self.get_ray(i, j);
+                    pixel_colors[(j * self.image_width + i) as usize] +=
+                        self.integrator.ray_color(&r, world, &mut self.rng);
+                }
+            }
         }
 
-        let unit_direction = r.direction();
-        let t = 0.5 * (unit_direction.y() + 1.0);
-        Color::new(1.0, 1.0, 1.0) * (1.0 - t) + Color::new(0.5, 0.7, 1.0) * t
+        ImageBuffer::from_fn(self.image_width, self.image_height, |i, j| {
+            let pixel_color = pixel_colors[(j * self.image_width + i) as usize];
+            (pixel_color / self.samples_per_pixel as f64).to_rgb32f()
+        })
     }
 
     fn get_ray(&mut self, i: u32, j: u32) -> Ray {
@@ -205,6 +194,31 @@ impl Camera {
 }
 
 impl Camera {
+    pub fn projection(&self) -> Projection {
+        let lens_radius = self.depth_of_field_disk_u.norm();
+        let viewport_width = self.pixel_delta_u.norm() * self.image_width as f64;
+        let viewport_height = self.pixel_delta_v.norm() * self.image_height as f64;
+
+        Projection {
+            image_width: self.image_width,
+            image_height: self.image_height,
+            origin: self.origin,
+            w: self.w,
+            upper_left: self.pixel00_loc - (self.pixel_delta_u + self.pixel_delta_v) / 2.0,
+            pixel_delta_u: self.pixel_delta_u,
+            pixel_delta_v: self.pixel_delta_v,
+            focus_dist: self.focus_dist,
+            lens_u: self.depth_of_field_disk_u,
+            lens_v: self.depth_of_field_disk_v,
+            lens_area: if lens_radius > 0.0 {
+                PI * lens_radius * lens_radius
+            } else {
+                1.0
+            },
+            film_area: viewport_width * viewport_height / (self.focus_dist * self.focus_dist),
+        }
+    }
+
     pub fn aggregate(base_image: &mut RgbImage, images: &Vec<RgbImage>) {
         for (x, y, pixel) in tqdm(base_image.enumerate_pixels_mut()) {
             let mut color = Color::new(0.0, 0.0, 0.0);
@@ -222,3 +236,92 @@ impl Camera {
         }
     }
 }
+
+// Camera geometry for tracing paths that end on the lens, as seen from the scene
+#[derive(Debug, Clone, Default)]
+pub struct Projection {
+    image_width: u32,
+    image_height: u32,
+    origin: Point3,
+    w: Vec3,
+    upper_left: Point3,
+    pixel_delta_u: Vec3,
+    pixel_delta_v: Vec3,
+    focus_dist: f64,
+    lens_u: Vec3,
+    lens_v: Vec3,
+    lens_area: f64, // One for a pinhole
+    film_area: f64, // Visible image plane at unit distance
+}
+
+impl Projection {
+    pub fn image_width(&self) -> u32 {
+        self.image_width
+    }
+
+    pub fn image_height(&self) -> u32 {
+        self.image_height
+    }
+
+    pub fn sample_lens(&self, rng: &mut Sampler) -> Point3 {
+        let p = Vec3::random_unit_disk_vector(rng);
+        self.origin + self.lens_u * p.x() + self.lens_v * p.y()
+    }
+
+    // Camera ray through the continuous raster position (`x`, `y`)
+    pub fn ray(&self, x: f64, y: f64, rng: &mut Sampler) -> Ray {
+        let origin = self.sample_lens(rng);
+        let target = self.upper_left + x * self.pixel_delta_u + y * self.pixel_delta_v;
+        Ray::new(origin, target - origin, rng.gen())
+    }
+
+    // Solid angle density at `p` of sampling `p_lens` through `sample_lens`
+    pub fn pdf_lens(&self, p_lens: &Point3, p: &Point3) -> f64 {
+        let to_lens = p_lens - p;
+        let cos_lens = to_lens.normalize().dot(&self.w).abs();
+        if cos_lens <= 0.0 {
+            return 0.0;
+        }
+        to_lens.norm_squared() / (cos_lens * self.lens_area)
+    }
+
+    // Pixel seeing `p` through `p_lens`
+    pub fn raster(&self, p_lens: &Point3, p: &Point3) -> Option<(u32, u32)> {
+        let direction = p - p_lens;
+        let cos_theta = -direction.dot(&self.w);
+        if cos_theta <= 0.0 {
+            return None;
+        }
+
+        let focus_point = p_lens + direction * (self.focus_dist / cos_theta);
+        let offset = focus_point - self.upper_left;
+        let x = offset.dot(&self.pixel_delta_u) / self.pixel_delta_u.norm_squared();
+        let y = offset.dot(&self.pixel_delta_v) / self.pixel_delta_v.norm_squared();
+
+        if x < 0.0 || y < 0.0 || x >= self.image_width as f64 || y >= self.image_height as f64 {
+            return None;
+        }
+        Some((x as u32, y as u32))
+    }
+
+    // Importance emitted from `p_lens` towards `p`, zero outside of the image
+    pub fn importance(&self, p_lens: &Point3, p: &Point3) -> f64 {
+        let Some(cos_theta) = self.visible_cos(p_lens, p) else {
+            return 0.0;
+        };
+        1.0 / (self.film_area * self.lens_area * cos_theta.powi(4))
+    }
+
+    // Solid angle density of camera rays from `p_lens` towards `p`
+    pub fn pdf_direction(&self, p_lens: &Point3, p: &Point3) -> f64 {
+        let Some(cos_theta) = self.visible_cos(p_lens, p) else {
+            return 0.0;
+        };
+        1.0 / (self.film_area * cos_theta.powi(3))
+    }
+
+    fn visible_cos(&self, p_lens: &Point3, p: &Point3) -> Option<f64> {
+        self.raster(p_lens, p)?;
+        Some(-(p - p_lens).normalize().dot(&self.w))
+    }
+}
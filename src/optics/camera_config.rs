@@ -1,3 +1,4 @@
+use crate::integrators::IntegratorType;
 use crate::utils::{Point3, Vec3};
 
 pub struct RenderOutputConfig {
@@ -5,6 +6,7 @@ pub struct RenderOutputConfig {
     pub image_width: u32,
     pub samples_per_pixel: u32,
     pub max_depth: u32,
+    pub integrator: IntegratorType,
 }
 
 impl Default for RenderOutputConfig {
@@ -14,6 +16,7 @@ impl Default for RenderOutputConfig {
             image_width: 100,
             samples_per_pixel: 100,
             max_depth: 50,
+            integrator: IntegratorType::default(),
         }
     }
 }
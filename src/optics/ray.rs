@@ -1,10 +1,34 @@
-use crate::utils::{Point3, Vec3};
+use crate::utils::{Color, Dispersion, Point3, SampledWavelengths, Vec3, Vec3Ext};
 
-#[derive(Debug)]
+// Dielectric a ray is travelling through, identified by the address of its material
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NestedMedium {
+    pub id: usize,
+    pub refraction_index: f64,
+    pub priority: u32,
+    pub absorption: Color, // Per unit length
+    pub dispersion: Option<Dispersion>,
+}
+
+impl NestedMedium {
+    // Index at the hero wavelength in spectral mode
+    pub fn refraction_index_at(&self, wavelengths: Option<&SampledWavelengths>) -> f64 {
+        match (self.dispersion, wavelengths) {
+            (Some(dispersion), Some(wavelengths)) => {
+                dispersion.refraction_index(wavelengths.hero())
+            }
+            _ => self.refraction_index,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Ray {
     origin: Point3,
     direction: Vec3,
     time: f64,
+    media: Vec<NestedMedium>,                // Innermost last, empty in air
+    wavelengths: Option<SampledWavelengths>, // Only set in spectral mode
 }
 
 impl Ray {
@@ -13,8 +37,27 @@ impl Ray {
             origin,
             direction: direction.normalize(), // Keeping the direction vector at unit length
             time,
+            media: Vec::new(),
+            wavelengths: None,
         }
     }
+
+    // Continues the path from a scattering event, still inside the same media
+    pub fn scattered(&self, origin: Point3, direction: Vec3) -> Ray {
+        let mut ray = Ray::new(origin, direction, self.time).with_media(self.media.clone());
+        ray.wavelengths = self.wavelengths;
+        ray
+    }
+
+    pub fn with_media(mut self, media: Vec<NestedMedium>) -> Ray {
+        self.media = media;
+        self
+    }
+
+    pub fn with_wavelengths(mut self, wavelengths: SampledWavelengths) -> Ray {
+        self.wavelengths = Some(wavelengths);
+        self
+    }
 }
 
 impl Ray {
@@ -29,10 +72,31 @@ impl Ray {
     pub fn time(&self) -> f64 {
         self.time
     }
+
+    pub fn media(&self) -> &[NestedMedium] {
+        &self.media
+    }
+
+    pub fn wavelengths(&self) -> Option<&SampledWavelengths> {
+        self.wavelengths.as_ref()
+    }
+
+    // Medium present where the ray's media overlap, the innermost one among equal priorities
+    pub fn medium(&self) -> Option<&NestedMedium> {
+        self.media.iter().max_by_key(|medium| medium.priority)
+    }
 }
 
 impl Ray {
     pub fn at(&self, t: f64) -> Point3 {
         self.origin + self.direction * t
     }
+
+    // Beer-Lambert absorption by the medium along the first `t` of the ray
+    pub fn transmittance(&self, t: f64) -> Color {
+        match self.medium() {
+            Some(medium) => (-medium.absorption * t).map(f64::exp),
+            None => Color::ones(),
+        }
+    }
 }
@@ -0,0 +1,24 @@
+use std::rc::Rc;
+
+use crate::textures::Texture;
+use crate::utils::{Color, Vec3, Vec3Ext};
+
+// Single channel of another texture as a gray level, e.g. from packed glTF metallic-roughness maps
+#[derive(Debug)]
+pub struct Channel {
+    texture: Rc<dyn Texture>,
+    index: usize,
+}
+
+impl Channel {
+    pub fn new(texture: Rc<dyn Texture>, index: usize) -> Self {
+        assert!(index < 3, "Textures have three channels");
+        Self { texture, index }
+    }
+}
+
+impl Texture for Channel {
+    fn sample(&self, u: f64, v: f64, p: &Vec3) -> Color {
+        Color::constant(self.texture.sample(u, v, p)[self.index])
+    }
+}
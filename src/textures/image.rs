@@ -1,18 +1,31 @@
 extern crate image;
-use image::RgbImage;
+use image::RgbaImage;
+use std::rc::Rc;
 
 use crate::textures::Texture;
-use crate::utils::{Color, Vec3};
+use crate::utils::{Color, Vec3, Vec3Ext};
 
 #[derive(Debug)]
 pub struct UVImage {
-    image: RgbImage,
+    image: Rc<RgbaImage>,
+    alpha: bool, // Samples the alpha channel as a gray level instead of the color
 }
 
 impl UVImage {
     pub fn new(img_path: &str) -> Self {
-        let img = image::open(img_path).unwrap().to_rgb8();
-        Self { image: img }
+        let img = image::open(img_path).unwrap().to_rgba8();
+        Self {
+            image: Rc::new(img),
+            alpha: false,
+        }
+    }
+
+    // Opacity of the same image, fully opaque where it has no alpha channel
+    pub fn alpha_mask(&self) -> Self {
+        Self {
+            image: self.image.clone(),
+            alpha: true,
+        }
     }
 }
 
@@ -26,6 +39,9 @@ impl Texture for UVImage {
         let i = i.min(width - 1);
         let j = j.min(height - 1);
         let pixel = self.image.get_pixel(i, j);
+        if self.alpha {
+            return Color::constant(pixel[3] as f64 / 255.0);
+        }
         Color::new(
             pixel[0] as f64 / 255.0,
             pixel[1] as f64 / 255.0,
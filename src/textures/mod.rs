@@ -1,9 +1,11 @@
+pub mod channel;
 mod checker;
 pub mod image;
 pub mod perlin;
 pub mod solid;
 pub mod texture;
 
+pub use channel::*;
 pub use checker::*;
 pub use image::*;
 pub use perlin::*;
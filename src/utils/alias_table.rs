@@ -0,0 +1,100 @@
+#[derive(Debug, Clone)]
+struct AliasBin {
+    q: f64,
+    pmf: f64,
+    alias: usize,
+}
+
+// Vose's alias method, O(1) sampling of a discrete distribution
+#[derive(Debug, Clone)]
+pub struct AliasTable {
+    bins: Vec<AliasBin>,
+}
+
+impl AliasTable {
+    pub fn new(weights: &[f64]) -> Self {
+        let sum: f64 = weights.iter().sum();
+        let n = weights.len();
+
+        let mut bins: Vec<AliasBin> = weights
+            .iter()
+            .map(|w| AliasBin {
+                q: 0.0,
+                pmf: if sum > 0.0 { w / sum } else { 0.0 },
+                alias: usize::MAX,
+            })
+            .collect();
+
+        let mut under = Vec::new();
+        let mut over = Vec::new();
+        for (i, bin) in bins.iter().enumerate() {
+            let p_hat = bin.pmf * n as f64;
+            if p_hat < 1.0 {
+                under.push((i, p_hat));
+            } else {
+                over.push((i, p_hat));
+            }
+        }
+
+        while let (Some(&(u_idx, u_p)), Some(&(o_idx, o_p))) = (under.last(), over.last()) {
+            under.pop();
+            over.pop();
+
+            bins[u_idx].q = u_p;
+            bins[u_idx].alias = o_idx;
+
+            let excess = u_p + o_p - 1.0;
+            if excess < 1.0 {
+                under.push((o_idx, excess));
+            } else {
+                over.push((o_idx, excess));
+            }
+        }
+
+        // Remaining bins are (up to rounding) exactly full
+        for (i, _) in under.into_iter().chain(over) {
+            bins[i].q = 1.0;
+            bins[i].alias = usize::MAX;
+        }
+
+        Self { bins }
+    }
+}
+
+impl AliasTable {
+    pub fn len(&self) -> usize {
+        self.bins.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bins.is_empty()
+    }
+
+    pub fn pmf(&self, idx: usize) -> f64 {
+        self.bins[idx].pmf
+    }
+
+    // Returns the sampled index and its probability, `u` in [0, 1)
+    pub fn sample(&self, u: f64) -> Option<(usize, f64)> {
+        if self.bins.is_empty() {
+            return None;
+        }
+
+        let n = self.bins.len();
+        let offset = ((u * n as f64) as usize).min(n - 1);
+        let up = (u * n as f64 - offset as f64).min(1.0 - f64::EPSILON);
+
+        let bin = &self.bins[offset];
+        let idx = if up < bin.q || bin.alias == usize::MAX {
+            offset
+        } else {
+            bin.alias
+        };
+
+        let pmf = self.bins[idx].pmf;
+        if pmf <= 0.0 {
+            return None;
+        }
+        Some((idx, pmf))
+    }
+}
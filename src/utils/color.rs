@@ -1,4 +1,4 @@
-pub use crate::utils::{Interval, Vec3, Vec3Ext};
+pub use crate::utils::{blackbody_rgb, Interval, Vec3, Vec3Ext};
 use image::Rgb;
 
 pub type Color = Vec3;
@@ -19,10 +19,31 @@ where
         self.z()
     }
 
+    // Unit luminance color of a blackbody at `temperature` Kelvin
+    fn blackbody(temperature: f64) -> Color {
+        blackbody_rgb(temperature)
+    }
+
+    fn luminance(&self) -> f64 {
+        0.2126 * self.r() + 0.7152 * self.g() + 0.0722 * self.b()
+    }
+
     fn linear_to_gamma(&self) -> Color {
         Color::new(self.r().sqrt(), self.g().sqrt(), self.b().sqrt())
     }
 
+    fn gamma_to_linear(&self) -> Color {
+        Color::new(self.r().powi(2), self.g().powi(2), self.b().powi(2))
+    }
+
+    fn to_rgb32f(&self) -> Rgb<f32> {
+        Rgb([self.r() as f32, self.g() as f32, self.b() as f32])
+    }
+
+    fn from_rgb32f(pixel: &Rgb<f32>) -> Color {
+        Color::new(pixel[0] as f64, pixel[1] as f64, pixel[2] as f64)
+    }
+
     fn to_rgb8(&self) -> Rgb<u8> {
         let intensity = Interval::new(0.0, 0.999);
 
@@ -12,6 +12,10 @@ impl Interval {
         Self::new(min, f64::INFINITY)
     }
 
+    pub fn universe() -> Self {
+        Self::new(f64::NEG_INFINITY, f64::INFINITY)
+    }
+
     pub fn wrap_intervals(a: &Interval, b: &Interval) -> Self {
         Self::new(a.min.min(b.min), a.max.max(b.max))
     }
@@ -1,10 +1,18 @@
+pub mod alias_table;
 pub mod color;
 pub mod interval;
+pub mod onb;
 pub mod perlin;
+pub mod sampler;
+pub mod spectrum;
 pub mod vec3;
 
 // Export
+pub use alias_table::*;
 pub use color::*;
 pub use interval::*;
+pub use onb::*;
 pub use perlin::*;
+pub use sampler::*;
+pub use spectrum::*;
 pub use vec3::*;
@@ -0,0 +1,58 @@
+use crate::utils::{Vec3, Vec3Ext};
+
+#[derive(Debug, Clone)]
+pub struct Onb {
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+}
+
+impl Onb {
+    pub fn from_w(n: &Vec3) -> Self {
+        let w = n.normalize();
+        let a = if w.x().abs() > 0.9 {
+            Vec3::new(0.0, 1.0, 0.0)
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let v = w.cross(&a).normalize();
+        let u = v.cross(&w);
+
+        Self { u, v, w }
+    }
+
+    // Frame around `n` with `u` along `tangent` projected onto its plane, arbitrary if degenerate
+    pub fn from_wu(n: &Vec3, tangent: &Vec3) -> Self {
+        let w = n.normalize();
+        let u = tangent - w * w.dot(tangent);
+        if u.norm_squared() < 1e-12 {
+            return Self::from_w(&w);
+        }
+        let u = u.normalize();
+        let v = w.cross(&u);
+
+        Self { u, v, w }
+    }
+}
+
+impl Onb {
+    pub fn u(&self) -> Vec3 {
+        self.u
+    }
+
+    pub fn v(&self) -> Vec3 {
+        self.v
+    }
+
+    pub fn w(&self) -> Vec3 {
+        self.w
+    }
+
+    pub fn to_world(&self, a: &Vec3) -> Vec3 {
+        a.x() * self.u + a.y() * self.v + a.z() * self.w
+    }
+
+    pub fn to_local(&self, a: &Vec3) -> Vec3 {
+        Vec3::new(a.dot(&self.u), a.dot(&self.v), a.dot(&self.w))
+    }
+}
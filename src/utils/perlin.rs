@@ -1,5 +1,4 @@
-use crate::utils::{Point3, Vec3, Vec3Ext};
-use rand::rngs::ThreadRng;
+use crate::utils::{Point3, Sampler, Vec3, Vec3Ext};
 use rand::Rng;
 
 #[derive(Debug)]
@@ -13,7 +12,7 @@ pub struct Perlin {
 impl Default for Perlin {
     fn default() -> Self {
         let point_count = 256;
-        let mut rng = rand::thread_rng();
+        let mut rng = Sampler::default();
         let mut rand_vec = Vec::with_capacity(point_count);
         for _ in 0..point_count {
             rand_vec.push((Vec3::random_uniform_vector(&mut rng) * 2.0 - Vec3::ones()).normalize());
@@ -33,13 +32,13 @@ impl Default for Perlin {
 }
 
 impl Perlin {
-    fn perlin_generate_perm(rng: &mut ThreadRng) -> Vec<usize> {
+    fn perlin_generate_perm(rng: &mut Sampler) -> Vec<usize> {
         let mut perm: Vec<usize> = (0..256).collect();
         Self::permute(rng, &mut perm);
         perm
     }
 
-    fn permute(rng: &mut ThreadRng, p: &mut [usize]) {
+    fn permute(rng: &mut Sampler, p: &mut [usize]) {
         for i in (1..p.len()).rev() {
             let target = rng.gen_range(0..=i);
             p.swap(i, target);
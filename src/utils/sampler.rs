@@ -0,0 +1,158 @@
+use rand::prelude::{Rng, SeedableRng, StdRng, ThreadRng};
+use rand::{thread_rng, RngCore};
+use rand_distr::StandardNormal;
+
+// Source of the random numbers consumed while tracing a path
+#[derive(Debug, Clone)]
+pub enum Sampler {
+    Independent(ThreadRng),
+    PrimarySample(Box<PrimarySampleSpace>),
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Sampler::Independent(thread_rng())
+    }
+}
+
+impl Sampler {
+    fn next_f64(&mut self) -> f64 {
+        match self {
+            Sampler::Independent(rng) => rng.gen(),
+            Sampler::PrimarySample(space) => space.next(),
+        }
+    }
+}
+
+impl RngCore for Sampler {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            Sampler::Independent(rng) => rng.next_u32(),
+            Sampler::PrimarySample(_) => (self.next_f64() * (1u64 << 32) as f64) as u32,
+        }
+    }
+
+    // Keeps the upper 53 bits, so that `gen::<f64>()` returns the primary sample itself
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            Sampler::Independent(rng) => rng.next_u64(),
+            Sampler::PrimarySample(_) => ((self.next_f64() * (1u64 << 53) as f64) as u64) << 11,
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_via_u64(self, dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+// Bytes for `RngCore::fill_bytes` of generators that produce whole `u64` values
+pub fn fill_bytes_via_u64(rng: &mut impl RngCore, dest: &mut [u8]) {
+    for chunk in dest.chunks_mut(8) {
+        let bytes = rng.next_u64().to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct PrimarySample {
+    value: f64,
+    last_modified: u64,
+    value_backup: f64,
+    modified_backup: u64,
+}
+
+// Vector of uniform numbers in [0, 1) which is mutated instead of redrawn between iterations
+// (Kelemen et al.), extended lazily as the path consumes more numbers
+#[derive(Debug, Clone)]
+pub struct PrimarySampleSpace {
+    rng: StdRng,
+    sigma: f64,
+    large_step_probability: f64,
+    samples: Vec<PrimarySample>,
+    index: usize,
+    iteration: u64,
+    large_step: bool,
+    last_large_step: u64,
+}
+
+impl PrimarySampleSpace {
+    pub fn new(seed: u64, sigma: f64, large_step_probability: f64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            sigma,
+            large_step_probability,
+            samples: vec![],
+            index: 0,
+            iteration: 0,
+            large_step: true,
+            last_large_step: 0,
+        }
+    }
+}
+
+impl PrimarySampleSpace {
+    // Decides between a large step, which redraws every number, and a small perturbation
+    pub fn start_iteration(&mut self) {
+        self.iteration += 1;
+        self.large_step = self.rng.gen::<f64>() < self.large_step_probability;
+        self.index = 0;
+    }
+
+    pub fn accept(&mut self) {
+        if self.large_step {
+            self.last_large_step = self.iteration;
+        }
+    }
+
+    // Restores the numbers of the last accepted iteration
+    pub fn reject(&mut self) {
+        for sample in &mut self.samples {
+            if sample.last_modified == self.iteration {
+                sample.value = sample.value_backup;
+                sample.last_modified = sample.modified_backup;
+            }
+        }
+        self.iteration -= 1;
+    }
+
+    fn next(&mut self) -> f64 {
+        if self.index >= self.samples.len() {
+            self.samples.push(PrimarySample {
+                value: self.rng.gen(),
+                last_modified: self.last_large_step,
+                ..Default::default()
+            });
+        }
+        let index = self.index;
+        self.index += 1;
+
+        let sample = &mut self.samples[index];
+
+        // Catch up with a large step that happened before the number was first used
+        if sample.last_modified < self.last_large_step {
+            sample.value = self.rng.gen();
+            sample.last_modified = self.last_large_step;
+        }
+
+        sample.value_backup = sample.value;
+        sample.modified_backup = sample.last_modified;
+
+        if self.large_step {
+            sample.value = self.rng.gen();
+        } else {
+            // Applies all small steps missed since the last modification at once
+            let steps = (self.iteration - sample.last_modified) as f64;
+            let offset: f64 = self.rng.sample(StandardNormal);
+            sample.value += offset * self.sigma * steps.sqrt();
+            sample.value -= sample.value.floor();
+        }
+        sample.last_modified = self.iteration;
+
+        sample.value
+    }
+}
@@ -0,0 +1,313 @@
+use nalgebra::Vector4;
+use std::sync::OnceLock;
+
+use crate::utils::{Color, ColorExt, Vec3, Vec3Ext};
+
+pub const LAMBDA_MIN: f64 = 360.0;
+pub const LAMBDA_MAX: f64 = 830.0;
+
+// Wavelengths traced together along a path, the first one is the hero wavelength
+pub const SPECTRUM_SAMPLES: usize = 4;
+
+// Spectral quantity at the sampled wavelengths
+pub type SampledSpectrum = Vector4<f64>;
+
+// CIE 1931 standard observer, multi-lobe fit by Wyman et al. 2013 (lambda in nm)
+pub fn cie_xyz(lambda: f64) -> Vec3 {
+    fn g(lambda: f64, mu: f64, sigma_lo: f64, sigma_hi: f64) -> f64 {
+        let t = (lambda - mu) / if lambda < mu { sigma_lo } else { sigma_hi };
+        (-0.5 * t * t).exp()
+    }
+
+    Vec3::new(
+        1.056 * g(lambda, 599.8, 37.9, 31.0) + 0.362 * g(lambda, 442.0, 16.0, 26.7)
+            - 0.065 * g(lambda, 501.1, 20.4, 26.2),
+        0.821 * g(lambda, 568.8, 46.9, 40.5) + 0.286 * g(lambda, 530.9, 16.3, 31.1),
+        1.217 * g(lambda, 437.0, 11.8, 36.0) + 0.681 * g(lambda, 459.0, 26.0, 13.8),
+    )
+}
+
+// CIE XYZ to linear sRGB (Rec. 709 primaries, D65 white)
+pub fn xyz_to_rgb(xyz: &Vec3) -> Color {
+    Color::new(
+        3.2404542 * xyz.x() - 1.5371385 * xyz.y() - 0.4985314 * xyz.z(),
+        -0.9692660 * xyz.x() + 1.8760108 * xyz.y() + 0.0415560 * xyz.z(),
+        0.0556434 * xyz.x() - 0.2040259 * xyz.y() + 1.0572252 * xyz.z(),
+    )
+}
+
+// Spectral radiance of a blackbody in W/(sr m^2 m), lambda in nm
+pub fn planck(lambda: f64, temperature: f64) -> f64 {
+    if temperature <= 0.0 {
+        return 0.0;
+    }
+
+    let c = 299792458.0;
+    let h = 6.62606957e-34;
+    let kb = 1.3806488e-23;
+    let l = lambda * 1e-9;
+
+    2.0 * h * c * c / (l.powi(5) * ((h * c / (l * kb * temperature)).exp() - 1.0))
+}
+
+// Temperatures tabulated for `blackbody_rgb`, in Kelvin
+const BLACKBODY_TABLE_MIN: f64 = 500.0;
+const BLACKBODY_TABLE_STEP: f64 = 50.0;
+const BLACKBODY_TABLE_SIZE: usize = 800;
+
+// Chromaticity of a blackbody in the RGB working space, normalized to unit luminance.
+// Interpolated from a table built on first use, volumes look it up at every emissive collision.
+pub fn blackbody_rgb(temperature: f64) -> Color {
+    static TABLE: OnceLock<Vec<Color>> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        (0..BLACKBODY_TABLE_SIZE)
+            .map(|i| integrate_blackbody(BLACKBODY_TABLE_MIN + i as f64 * BLACKBODY_TABLE_STEP))
+            .collect()
+    });
+
+    let x = (temperature - BLACKBODY_TABLE_MIN) / BLACKBODY_TABLE_STEP;
+    if x < 0.0 || x >= (BLACKBODY_TABLE_SIZE - 1) as f64 {
+        return integrate_blackbody(temperature);
+    }
+
+    // Both ends have unit luminance, and so does anything in between
+    let i = x as usize;
+    let t = x - i as f64;
+    table[i] * (1.0 - t) + table[i + 1] * t
+}
+
+fn integrate_blackbody(temperature: f64) -> Color {
+    let mut xyz = Vec3::zeros();
+    let mut lambda = LAMBDA_MIN;
+    while lambda <= LAMBDA_MAX {
+        xyz += cie_xyz(lambda) * planck(lambda, temperature);
+        lambda += 1.0;
+    }
+
+    if xyz.y() <= 0.0 {
+        return Color::zeros();
+    }
+
+    // Very low temperatures fall slightly outside the gamut, clamping changes their luminance
+    let rgb = xyz_to_rgb(&(xyz / xyz.y())).map(|c| c.max(0.0));
+    let luminance = rgb.luminance();
+    if luminance <= 0.0 {
+        return Color::zeros();
+    }
+    rgb / luminance
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Illuminant {
+    Candle,
+    Incandescent,
+    Halogen,
+    Fluorescent,
+    Daylight,
+    Overcast,
+}
+
+impl Illuminant {
+    pub fn temperature(&self) -> f64 {
+        match self {
+            Illuminant::Candle => 1850.0,
+            Illuminant::Incandescent => 2700.0,
+            Illuminant::Halogen => 3200.0,
+            Illuminant::Fluorescent => 4100.0,
+            Illuminant::Daylight => 6500.0,
+            Illuminant::Overcast => 7500.0,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        blackbody_rgb(self.temperature())
+    }
+}
+
+// Smits 1999 basis spectra, ten bins from 380 to 720 nm
+const SMITS_WHITE: [f64; 10] = [
+    1.0000, 1.0000, 0.9999, 0.9993, 0.9992, 0.9998, 1.0000, 1.0000, 1.0000, 1.0000,
+];
+const SMITS_CYAN: [f64; 10] = [
+    0.9710, 0.9426, 1.0007, 1.0007, 1.0007, 1.0007, 0.1564, 0.0000, 0.0000, 0.0000,
+];
+const SMITS_MAGENTA: [f64; 10] = [
+    1.0000, 1.0000, 0.9685, 0.2229, 0.0000, 0.0458, 0.8369, 1.0000, 1.0000, 0.9959,
+];
+const SMITS_YELLOW: [f64; 10] = [
+    0.0001, 0.0000, 0.1088, 0.6651, 1.0000, 1.0000, 0.9996, 0.9586, 0.9685, 0.9840,
+];
+const SMITS_RED: [f64; 10] = [
+    0.1012, 0.0515, 0.0000, 0.0000, 0.0000, 0.0000, 0.8325, 1.0149, 1.0149, 1.0149,
+];
+const SMITS_GREEN: [f64; 10] = [
+    0.0000, 0.0000, 0.0273, 0.7937, 1.0000, 0.9418, 0.1719, 0.0000, 0.0000, 0.0025,
+];
+const SMITS_BLUE: [f64; 10] = [
+    1.0000, 1.0000, 0.8916, 0.3323, 0.0000, 0.0000, 0.0003, 0.0369, 0.0483, 0.0496,
+];
+
+// Linear interpolation between the bin centers, constant beyond the outer ones
+fn smits_basis(basis: &[f64; 10], lambda: f64) -> f64 {
+    let x = ((lambda - 380.0) / 34.0 - 0.5).clamp(0.0, 9.0);
+    let i = (x as usize).min(8);
+    let t = x - i as f64;
+    basis[i] * (1.0 - t) + basis[i + 1] * t
+}
+
+// Smooth spectrum reproducing `rgb`, evaluated at `lambda`. Colors within [0, 1] stay reflectances
+pub fn upsample_rgb(rgb: &Color, lambda: f64) -> f64 {
+    let (r, g, b) = (rgb.x().max(0.0), rgb.y().max(0.0), rgb.z().max(0.0));
+    let basis = |spectrum: &[f64; 10]| smits_basis(spectrum, lambda);
+
+    // White for the smallest channel, then the secondary and primary colors on top
+    if r <= g && r <= b {
+        r * basis(&SMITS_WHITE)
+            + if g <= b {
+                (g - r) * basis(&SMITS_CYAN) + (b - g) * basis(&SMITS_BLUE)
+            } else {
+                (b - r) * basis(&SMITS_CYAN) + (g - b) * basis(&SMITS_GREEN)
+            }
+    } else if g <= r && g <= b {
+        g * basis(&SMITS_WHITE)
+            + if r <= b {
+                (r - g) * basis(&SMITS_MAGENTA) + (b - r) * basis(&SMITS_BLUE)
+            } else {
+                (b - g) * basis(&SMITS_MAGENTA) + (r - b) * basis(&SMITS_RED)
+            }
+    } else {
+        b * basis(&SMITS_WHITE)
+            + if r <= g {
+                (r - b) * basis(&SMITS_YELLOW) + (g - r) * basis(&SMITS_GREEN)
+            } else {
+                (g - b) * basis(&SMITS_YELLOW) + (r - g) * basis(&SMITS_RED)
+            }
+    }
+}
+
+// Hero wavelength with equally spaced rotations, each importance sampled towards the visible range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampledWavelengths {
+    lambda: [f64; SPECTRUM_SAMPLES],
+    pdf: [f64; SPECTRUM_SAMPLES],
+}
+
+impl SampledWavelengths {
+    pub fn sample_visible(u: f64) -> Self {
+        let mut lambda = [0.0; SPECTRUM_SAMPLES];
+        let mut pdf = [0.0; SPECTRUM_SAMPLES];
+        for i in 0..SPECTRUM_SAMPLES {
+            let u = (u + i as f64 / SPECTRUM_SAMPLES as f64).fract();
+            lambda[i] = 538.0 - 138.888889 * (0.85691062 - 1.82750197 * u).atanh();
+            pdf[i] = 0.0039398042 / (0.0072 * (lambda[i] - 538.0)).cosh().powi(2);
+        }
+
+        Self { lambda, pdf }
+    }
+
+    pub fn hero(&self) -> f64 {
+        self.lambda[0]
+    }
+
+    pub fn lambda(&self) -> &[f64; SPECTRUM_SAMPLES] {
+        &self.lambda
+    }
+
+    // Wavelength dependent scattering, only the hero wavelength can follow the path from here
+    pub fn terminate_secondary(&mut self) {
+        if self.secondary_terminated() {
+            return;
+        }
+        self.pdf[1..].fill(0.0);
+        self.pdf[0] /= SPECTRUM_SAMPLES as f64;
+    }
+
+    pub fn secondary_terminated(&self) -> bool {
+        self.pdf[1..].iter().all(|&pdf| pdf == 0.0)
+    }
+
+    pub fn upsample(&self, rgb: &Color) -> SampledSpectrum {
+        SampledSpectrum::from_fn(|i, _| upsample_rgb(rgb, self.lambda[i]))
+    }
+}
+
+// Index of refraction varying with the wavelength, lambda in nm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dispersion {
+    Cauchy(f64, f64),              // A + B / lambda^2, B in um^2
+    Sellmeier([f64; 3], [f64; 3]), // B and C coefficients, C in um^2
+}
+
+impl Dispersion {
+    // Borosilicate crown glass, the common optical glass
+    pub fn bk7() -> Self {
+        Dispersion::Sellmeier(
+            [1.03961212, 0.231792344, 1.01046945],
+            [0.00600069867, 0.0200179144, 103.560653],
+        )
+    }
+
+    // Dense flint glass, strongly dispersive
+    pub fn sf11() -> Self {
+        Dispersion::Sellmeier(
+            [1.73759695, 0.313747346, 1.89878101],
+            [0.013188707, 0.0623068142, 155.23629],
+        )
+    }
+
+    pub fn refraction_index(&self, lambda: f64) -> f64 {
+        let l2 = (lambda * 1e-3).powi(2);
+        match self {
+            Dispersion::Cauchy(a, b) => a + b / l2,
+            Dispersion::Sellmeier(b, c) => {
+                (1.0 + (0..3).map(|i| b[i] * l2 / (l2 - c[i])).sum::<f64>()).sqrt()
+            }
+        }
+    }
+}
+
+// Converts radiance at the sampled wavelengths through CIE XYZ to RGB, white balanced such that a
+// flat spectrum stays neutral
+#[derive(Debug, Clone)]
+pub struct SpectralFilm {
+    y_integral: f64,
+    white_balance: Color,
+}
+
+impl SpectralFilm {
+    pub fn new() -> Self {
+        let mut flat = Vec3::zeros();
+        let mut lambda = LAMBDA_MIN;
+        while lambda <= LAMBDA_MAX {
+            flat += cie_xyz(lambda);
+            lambda += 1.0;
+        }
+
+        Self {
+            y_integral: flat.y(),
+            white_balance: xyz_to_rgb(&(flat / flat.y())).map(|c| 1.0 / c),
+        }
+    }
+}
+
+impl Default for SpectralFilm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpectralFilm {
+    pub fn to_xyz(&self, wavelengths: &SampledWavelengths, radiance: &SampledSpectrum) -> Vec3 {
+        let mut xyz = Vec3::zeros();
+        for i in 0..SPECTRUM_SAMPLES {
+            if wavelengths.pdf[i] > 0.0 {
+                xyz += cie_xyz(wavelengths.lambda[i]) * radiance[i] / wavelengths.pdf[i];
+            }
+        }
+        xyz / (SPECTRUM_SAMPLES as f64 * self.y_integral)
+    }
+
+    pub fn to_rgb(&self, wavelengths: &SampledWavelengths, radiance: &SampledSpectrum) -> Color {
+        xyz_to_rgb(&self.to_xyz(wavelengths, radiance)).component_mul(&self.white_balance)
+    }
+}
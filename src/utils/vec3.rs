@@ -1,10 +1,12 @@
 extern crate nalgebra as na;
 
 use na::Vector3 as _Vector3;
-use rand::prelude::{Rng, ThreadRng};
+use rand::prelude::Rng;
 use rand_distr::StandardNormal;
 use std::ops::Index;
 
+use crate::utils::Sampler;
+
 pub type Vec3 = _Vector3<f64>;
 pub type Point3 = Vec3;
 
@@ -45,22 +47,22 @@ where
 
     fn squared(&self) -> Self;
 
-    fn random_uniform_vector(rng: &mut ThreadRng) -> Vec3 {
+    fn random_uniform_vector(rng: &mut Sampler) -> Vec3 {
         Vec3::new(rng.gen(), rng.gen(), rng.gen())
     }
 
-    fn random_gaussian_vector(rng: &mut ThreadRng) -> Vec3 {
+    fn random_gaussian_vector(rng: &mut Sampler) -> Vec3 {
         Vec3::new(
             rng.sample(StandardNormal),
             rng.sample(StandardNormal),
             rng.sample(StandardNormal),
         )
     }
-    fn random_unit_sphere_vector(rng: &mut ThreadRng) -> Vec3 {
+    fn random_unit_sphere_vector(rng: &mut Sampler) -> Vec3 {
         Self::random_gaussian_vector(rng).normalize()
     }
 
-    fn random_hemisphere_vector(rng: &mut ThreadRng, normal: &Vec3) -> Vec3 {
+    fn random_hemisphere_vector(rng: &mut Sampler, normal: &Vec3) -> Vec3 {
         let on_unit_sphere = Self::random_unit_sphere_vector(rng);
         if on_unit_sphere.dot(normal) > 0.0 {
             on_unit_sphere
@@ -69,7 +71,7 @@ where
         }
     }
 
-    fn random_unit_disk_vector(rng: &mut ThreadRng) -> Vec3 {
+    fn random_unit_disk_vector(rng: &mut Sampler) -> Vec3 {
         let r = rng.gen::<f64>().sqrt();
         let theta = 2.0 * std::f64::consts::PI * rng.gen::<f64>();
 